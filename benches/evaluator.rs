@@ -0,0 +1,50 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use poker_rust::calc::lookup::{calc_set_strength, calc_strength};
+use poker_rust::card::{Card, CardSet};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+const HANDS: usize = 10_000;
+
+fn hands(size: usize) -> Vec<Vec<Card>> {
+    let mut deck: Vec<Card> = CardSet::DECK.into();
+    let mut rng: StdRng = StdRng::seed_from_u64(42);
+    (0..HANDS).map(|_| {
+        deck.shuffle(&mut rng);
+        deck[..size].to_vec()
+    }).collect()
+}
+
+fn bench_calc_strength(c: &mut Criterion) {
+    let mut group = c.benchmark_group("calc_strength");
+    group.throughput(Throughput::Elements(HANDS as u64));
+    for size in 5..=7 {
+        let hands: Vec<Vec<Card>> = hands(size);
+        group.bench_function(format!("{} cards", size), |b| {
+            b.iter(|| {
+                hands.iter()
+                    .map(|hand| calc_strength(black_box(hand)) as u64)
+                    .sum::<u64>()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_calc_set_strength(c: &mut Criterion) {
+    let mut group = c.benchmark_group("calc_set_strength");
+    group.throughput(Throughput::Elements(HANDS as u64));
+    let sets: Vec<CardSet> = hands(7).iter()
+        .map(CardSet::from)
+        .collect();
+    group.bench_function("7 cards", |b| {
+        b.iter(|| {
+            sets.iter()
+                .map(|&cards| calc_set_strength(black_box(cards)) as u64)
+                .sum::<u64>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_calc_strength, bench_calc_set_strength);
+criterion_main!(benches);
@@ -0,0 +1,23 @@
+use poker_rust::calc::chart::{preflop_chart, Chart};
+use poker_rust::calc::range::Range;
+use rand::thread_rng;
+use std::env;
+
+// Regenerates the preflop chart: preflop_chart [opponents] [trials] [text|csv|json] [opponent range]
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let opponents: usize = args.get(1).map_or(1, |arg| arg.parse().expect("Error: Invalid number of opponents"));
+    let trials: u64 = args.get(2).map_or(10_000, |arg| arg.parse().expect("Error: Invalid number of trials"));
+    let format: &str = args.get(3).map_or("text", |arg| arg.as_str());
+    let range: Range = match args.get(4) {
+        Some(range) => range.parse().expect("Error: Invalid opponent range"),
+        None => Range::any_two(),
+    };
+
+    let chart: Chart = preflop_chart(&vec![range; opponents], trials, &mut thread_rng());
+    match format {
+        "csv" => print!("{}", chart.to_csv()),
+        "json" => println!("{}", chart.to_json()),
+        _ => print!("{}", chart),
+    }
+}
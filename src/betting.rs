@@ -0,0 +1,656 @@
+use crate::pot::{build_pots, Pot};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+// Forced bets posted at the start of every hand
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Blinds {
+    pub small: u32,
+    pub big: u32,
+    pub ante: u32,
+}
+
+// How much a bet or raise can be
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Structure {
+    NoLimit,
+    // Raises can make the bet at most the size of the pot after calling
+    PotLimit,
+    // Bets and raises are the small bet for the first two rounds and the big bet after, with at
+    // most cap bets and raises a round. The big blind counts as the first bet before the flop
+    FixedLimit { small_bet: u32, big_bet: u32, cap: usize },
+}
+
+impl Display for Structure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Structure::NoLimit => write!(f, "No Limit"),
+            Structure::PotLimit => write!(f, "Pot Limit"),
+            Structure::FixedLimit { small_bet, big_bet, .. } => write!(f, "Fixed Limit {}/{}", small_bet, big_bet),
+        }
+    }
+}
+
+// Bet and Raise give the total the seat's bet is brought to this round, not the amount added
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    Bet(u32),
+    Raise(u32),
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Fold => write!(f, "Fold"),
+            Action::Check => write!(f, "Check"),
+            Action::Call => write!(f, "Call"),
+            Action::Bet(amount) => write!(f, "Bet {}", amount),
+            Action::Raise(amount) => write!(f, "Raise to {}", amount),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BetError {
+    // Nobody is left to act this round
+    BettingClosed,
+    // The amount needed to call
+    CannotCheck(u32),
+    NothingToCall,
+    // There's already a bet, so it has to be a raise
+    CannotBet,
+    NothingToRaise,
+    // The smallest total allowed
+    BelowMinimum(u32),
+    // The largest total the seat's chips allow
+    NotEnoughChips(u32),
+    // A short all-in doesn't reopen the betting for seats that already acted
+    RaiseNotAllowed,
+    // The most the betting structure allows
+    AboveLimit(u32),
+    // Fixed limit rounds allow only so many bets and raises
+    RaisesCapped,
+}
+
+impl Display for BetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BetError::BettingClosed => write!(f, "Error: Betting is closed"),
+            BetError::CannotCheck(to_call) => write!(f, "Error: Can't check, {} to call", to_call),
+            BetError::NothingToCall => write!(f, "Error: Nothing to call"),
+            BetError::CannotBet => write!(f, "Error: Can't bet, there's already a bet"),
+            BetError::NothingToRaise => write!(f, "Error: Nothing to raise"),
+            BetError::BelowMinimum(minimum) => write!(f, "Error: Must be at least {}", minimum),
+            BetError::NotEnoughChips(maximum) => write!(f, "Error: Can't be more than {}", maximum),
+            BetError::RaiseNotAllowed => write!(f, "Error: Raising isn't allowed"),
+            BetError::AboveLimit(limit) => write!(f, "Error: The limit is {}", limit),
+            BetError::RaisesCapped => write!(f, "Error: Betting is capped this round"),
+        }
+    }
+}
+
+impl Error for BetError {}
+
+// Chips and bets for every seat through a hand. Stacks carry over from hand to hand, and
+// seats without chips sit the hand out
+#[derive(Clone, Debug, PartialEq)]
+pub struct Betting {
+    blinds: Blinds,
+    structure: Structure,
+    stacks: Vec<u32>,
+    // Chips put in during the current round
+    committed: Vec<u32>,
+    // Chips put in during the whole hand, antes included
+    contributed: Vec<u32>,
+    folded: Vec<bool>,
+    // Seats that acted since the last full raise
+    acted: Vec<bool>,
+    current_bet: u32,
+    // Size of the last full bet or raise, the least the next raise can add
+    min_raise: u32,
+    // Bets and raises so far this round, blinds included
+    raises: usize,
+    // Rounds dealt this hand, 0 before the flop
+    round: usize,
+    to_act: Option<usize>,
+}
+
+impl Betting {
+    // No limit betting, see with_structure for the others
+    pub fn new(stacks: Vec<u32>, blinds: Blinds) -> Betting {
+        Betting::with_structure(stacks, blinds, Structure::NoLimit)
+    }
+
+    pub fn with_structure(stacks: Vec<u32>, blinds: Blinds, structure: Structure) -> Betting {
+        let seats: usize = stacks.len();
+        Betting {
+            blinds,
+            structure,
+            stacks,
+            committed: vec![0; seats],
+            contributed: vec![0; seats],
+            folded: vec![true; seats],
+            acted: vec![false; seats],
+            current_bet: 0,
+            min_raise: blinds.big,
+            raises: 0,
+            round: 0,
+            to_act: None,
+        }
+    }
+
+    // Posts antes and blinds, the small blind left of the button or on it heads-up
+    pub fn start_hand(&mut self, button: usize) {
+        self.post_antes();
+        self.start_round(button);
+        self.round = 0;
+        self.min_raise = self.bet_size();
+        let seated: usize = self.folded.iter().filter(|&&folded| !folded).count();
+        let small: usize = if seated == 2 { button } else { self.next_seated(button) };
+        let big: usize = self.next_seated(small);
+        self.put(small, self.blinds.small);
+        self.put(big, self.blinds.big);
+        self.current_bet = self.blinds.big;
+        self.raises = 1;
+        self.to_act = Some(big);
+        self.advance();
+    }
+
+    // Posts antes and a stud bring-in of the small blind, the seat bringing it in having acted
+    // unless someone completes to a full bet. Action starts left of the bring-in
+    pub fn start_bring_in(&mut self, bring_in: usize) {
+        self.post_antes();
+        self.start_round(bring_in);
+        self.round = 0;
+        self.put(bring_in, self.blinds.small);
+        self.current_bet = self.blinds.small;
+        // Completing the bring-in to a full bet counts as the first bet of the round
+        self.min_raise = self.bet_size().saturating_sub(self.blinds.small);
+        self.acted[bring_in] = true;
+        self.to_act = Some(bring_in);
+        self.advance();
+    }
+
+    // Starts a new betting round with the first seat left of the button
+    pub fn start_round(&mut self, button: usize) {
+        self.committed.iter_mut().for_each(|chips| *chips = 0);
+        self.acted.iter_mut().for_each(|acted| *acted = false);
+        self.current_bet = 0;
+        self.raises = 0;
+        self.round += 1;
+        self.min_raise = self.bet_size();
+        self.to_act = Some(button);
+        self.advance();
+    }
+
+    // Applies an action for the seat whose turn it is
+    pub fn act(&mut self, action: Action) -> Result<(), BetError> {
+        let seat: usize = self.to_act.ok_or(BetError::BettingClosed)?;
+        let to_call: u32 = self.to_call(seat);
+        match action {
+            Action::Fold => self.folded[seat] = true,
+            Action::Check if to_call > 0 => return Err(BetError::CannotCheck(to_call)),
+            Action::Check => {}
+            Action::Call if to_call == 0 => return Err(BetError::NothingToCall),
+            Action::Call => self.put(seat, to_call),
+            Action::Bet(_) if self.current_bet > 0 => return Err(BetError::CannotBet),
+            Action::Raise(_) if self.current_bet == 0 => return Err(BetError::NothingToRaise),
+            Action::Bet(total) | Action::Raise(total) => self.raise(seat, total)?,
+        }
+        self.acted[seat] = true;
+        self.advance();
+        Ok(())
+    }
+
+    // The seat whose turn it is, None once the round is over
+    pub fn to_act(&self) -> Option<usize> {
+        self.to_act
+    }
+
+    pub fn to_call(&self, seat: usize) -> u32 {
+        (self.current_bet - self.committed[seat]).min(self.stacks[seat])
+    }
+
+    pub fn current_bet(&self) -> u32 {
+        self.current_bet
+    }
+
+    // Smallest total a bet or raise can bring the current bet to
+    pub fn min_raise_to(&self) -> u32 {
+        self.current_bet + self.min_raise
+    }
+
+    // Largest total a seat can bet, the limit or every chip it has if that's less
+    pub fn max_raise_to(&self, seat: usize) -> u32 {
+        let limit: u32 = match self.structure {
+            Structure::NoLimit => u32::MAX,
+            // Call first, then raise by the whole pot including that call
+            Structure::PotLimit => self.current_bet + self.pot() + (self.current_bet - self.committed[seat]),
+            Structure::FixedLimit { .. } => self.min_raise_to(),
+        };
+        limit.min(self.all_in_to(seat))
+    }
+
+    // Whether the seat may bet or raise, having chips beyond a call and the betting open to it
+    pub fn can_raise(&self, seat: usize) -> bool {
+        !self.acted[seat] && !self.is_capped() && self.all_in_to(seat) > self.current_bet
+    }
+
+    pub fn structure(&self) -> Structure {
+        self.structure
+    }
+
+    // Adds chips to a seat's stack, meant for buying back in between hands
+    pub fn rebuy(&mut self, seat: usize, chips: u32) {
+        self.stacks[seat] += chips;
+    }
+
+    // Meant to be changed between hands, bet sizes are set as each round starts
+    pub fn set_structure(&mut self, structure: Structure) {
+        self.structure = structure;
+    }
+
+    pub fn pot(&self) -> u32 {
+        self.contributed.iter().sum()
+    }
+
+    pub fn blinds(&self) -> Blinds {
+        self.blinds
+    }
+
+    pub fn stacks(&self) -> &[u32] {
+        &self.stacks
+    }
+
+    pub fn contributed(&self) -> &[u32] {
+        &self.contributed
+    }
+
+    // Main pot and side pots from what each seat put in
+    pub fn pots(&self) -> Vec<Pot> {
+        build_pots(&self.contributed, &self.folded)
+    }
+
+    pub fn has_folded(&self, seat: usize) -> bool {
+        self.folded[seat]
+    }
+
+    pub fn is_all_in(&self, seat: usize) -> bool {
+        !self.folded[seat] && self.stacks[seat] == 0
+    }
+
+    // Seats that haven't folded
+    pub fn in_hand(&self) -> Vec<usize> {
+        (0..self.stacks.len())
+            .filter(|&seat| !self.folded[seat])
+            .collect()
+    }
+
+    // No more betting this hand, everyone else folded or at most one seat has chips left
+    pub fn is_closed(&self) -> bool {
+        self.to_act.is_none() && (self.in_hand().len() < 2 || self.can_bet() < 2)
+    }
+
+    // Gives back the part of the biggest contribution nobody else matched
+    pub fn return_uncalled(&mut self) {
+        let mut sorted: Vec<u32> = self.contributed.clone();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        if sorted.len() < 2 || sorted[0] == sorted[1] {
+            return;
+        }
+        let seat: usize = self.contributed.iter().position(|&chips| chips == sorted[0]).unwrap();
+        let uncalled: u32 = sorted[0] - sorted[1];
+        self.contributed[seat] -= uncalled;
+        self.stacks[seat] += uncalled;
+    }
+
+    // Splits the whole pot between the winners, the first ones listed getting any odd chips
+    pub fn award(&mut self, winners: &[usize]) {
+        let pot: u32 = self.pot();
+        let each: u32 = pot / winners.len() as u32;
+        let odd: usize = (pot % winners.len() as u32) as usize;
+        for (n, &seat) in winners.iter().enumerate() {
+            self.stacks[seat] += each + if n < odd { 1 } else { 0 };
+        }
+        self.contributed.iter_mut().for_each(|chips| *chips = 0);
+    }
+
+    // Hands out chips already split up, one share per seat
+    pub fn award_shares(&mut self, shares: &[u32]) {
+        for (stack, &share) in self.stacks.iter_mut().zip(shares) {
+            *stack += share;
+        }
+        self.contributed.iter_mut().for_each(|chips| *chips = 0);
+    }
+
+    // Seats with chips are dealt in, each putting in an ante if there is one
+    fn post_antes(&mut self) {
+        for seat in 0..self.stacks.len() {
+            self.folded[seat] = self.stacks[seat] == 0;
+            self.contributed[seat] = 0;
+        }
+        for seat in 0..self.stacks.len() {
+            if !self.folded[seat] {
+                let ante: u32 = self.blinds.ante.min(self.stacks[seat]);
+                self.stacks[seat] -= ante;
+                self.contributed[seat] += ante;
+            }
+        }
+    }
+
+    // Puts chips in, up to everything the seat has
+    fn put(&mut self, seat: usize, chips: u32) {
+        let chips: u32 = chips.min(self.stacks[seat]);
+        self.stacks[seat] -= chips;
+        self.committed[seat] += chips;
+        self.contributed[seat] += chips;
+    }
+
+    fn raise(&mut self, seat: usize, total: u32) -> Result<(), BetError> {
+        let chips: u32 = self.all_in_to(seat);
+        let maximum: u32 = self.max_raise_to(seat);
+        let all_in: bool = total == chips;
+        if self.acted[seat] {
+            return Err(BetError::RaiseNotAllowed);
+        }
+        if self.is_capped() {
+            return Err(BetError::RaisesCapped);
+        }
+        if total > chips {
+            return Err(BetError::NotEnoughChips(chips));
+        }
+        if total > maximum {
+            return Err(BetError::AboveLimit(maximum));
+        }
+        if total <= self.current_bet || (total < self.min_raise_to() && !all_in) {
+            return Err(BetError::BelowMinimum(self.min_raise_to().min(maximum)));
+        }
+
+        // Only a full raise reopens the betting for everyone else and counts toward the cap
+        let increase: u32 = total - self.current_bet;
+        if increase >= self.min_raise {
+            // Never less than a full bet, which a completed bring-in falls short of
+            self.min_raise = increase.max(self.bet_size());
+            self.raises += 1;
+            self.acted.iter_mut().for_each(|acted| *acted = false);
+        }
+        self.current_bet = total;
+        self.put(seat, total - self.committed[seat]);
+        Ok(())
+    }
+
+    // Total a seat's bet comes to if it puts in every chip
+    fn all_in_to(&self, seat: usize) -> u32 {
+        self.committed[seat] + self.stacks[seat]
+    }
+
+    // Size of a bet this round, the least one can be under no limit and pot limit
+    fn bet_size(&self) -> u32 {
+        match self.structure {
+            Structure::FixedLimit { small_bet, .. } if self.round < 2 => small_bet,
+            Structure::FixedLimit { big_bet, .. } => big_bet,
+            Structure::NoLimit | Structure::PotLimit => self.blinds.big,
+        }
+    }
+
+    fn is_capped(&self) -> bool {
+        match self.structure {
+            Structure::FixedLimit { cap, .. } => self.raises >= cap,
+            Structure::NoLimit | Structure::PotLimit => false,
+        }
+    }
+
+    // Moves the turn to the next seat that still has to act, if any
+    fn advance(&mut self) {
+        let start: usize = match self.to_act {
+            Some(seat) => seat,
+            None => return,
+        };
+        let seats: usize = self.stacks.len();
+        self.to_act = (1..=seats)
+            .map(|offset| (start + offset) % seats)
+            .find(|&seat| self.needs_to_act(seat));
+        if self.in_hand().len() < 2 {
+            self.to_act = None;
+        }
+    }
+
+    fn needs_to_act(&self, seat: usize) -> bool {
+        if self.folded[seat] || self.stacks[seat] == 0 {
+            return false;
+        }
+        self.committed[seat] < self.current_bet || (!self.acted[seat] && self.can_bet() > 1)
+    }
+
+    // Seats still in the hand with chips to bet
+    fn can_bet(&self) -> usize {
+        (0..self.stacks.len())
+            .filter(|&seat| !self.folded[seat] && self.stacks[seat] > 0)
+            .count()
+    }
+
+    fn next_seated(&self, seat: usize) -> usize {
+        let seats: usize = self.stacks.len();
+        (1..=seats)
+            .map(|offset| (seat + offset) % seats)
+            .find(|&next| !self.folded[next])
+            .unwrap_or(seat)
+    }
+}
+
+#[cfg(test)]
+mod betting_tests {
+    use crate::betting::{Action, BetError, Betting, Blinds, Structure};
+    use crate::pot::Pot;
+
+    const BLINDS: Blinds = Blinds { small: 5, big: 10, ante: 0 };
+
+    fn betting(stacks: &[u32], button: usize) -> Betting {
+        let mut betting: Betting = Betting::new(stacks.to_vec(), BLINDS);
+        betting.start_hand(button);
+        betting
+    }
+
+    #[test]
+    fn test_blinds_heads_up() {
+        // Heads-up the button posts the small blind and acts first before the flop
+        let betting: Betting = betting(&[1000, 1000], 0);
+        assert_eq!(betting.stacks(), &[995, 990]);
+        assert_eq!(betting.to_act(), Some(0));
+        assert_eq!(betting.to_call(0), 5);
+        assert_eq!(betting.pot(), 15);
+    }
+
+    #[test]
+    fn test_blinds_and_antes() {
+        let mut betting: Betting = Betting::new(vec![1000, 1000, 1000, 1000], Blinds { small: 5, big: 10, ante: 1 });
+        betting.start_hand(3);
+        assert_eq!(betting.stacks(), &[994, 989, 999, 999]);
+        assert_eq!(betting.to_act(), Some(2));
+        assert_eq!(betting.pot(), 19);
+    }
+
+    #[test]
+    fn test_big_blind_option() {
+        let mut betting: Betting = betting(&[1000, 1000, 1000], 0);
+        assert_eq!(betting.act(Action::Call), Ok(()));
+        assert_eq!(betting.act(Action::Call), Ok(()));
+        assert_eq!(betting.to_act(), Some(2));
+        assert_eq!(betting.act(Action::Check), Ok(()));
+        assert_eq!(betting.to_act(), None);
+        assert_eq!(betting.pot(), 30);
+    }
+
+    #[test]
+    fn test_invalid_actions() {
+        let mut betting: Betting = betting(&[1000, 1000], 0);
+        assert_eq!(betting.act(Action::Check), Err(BetError::CannotCheck(5)));
+        assert_eq!(betting.act(Action::Bet(20)), Err(BetError::CannotBet));
+        assert_eq!(betting.act(Action::Raise(15)), Err(BetError::BelowMinimum(20)));
+        assert_eq!(betting.act(Action::Raise(2000)), Err(BetError::NotEnoughChips(1000)));
+        assert_eq!(betting.act(Action::Call), Ok(()));
+        assert_eq!(betting.act(Action::Call), Err(BetError::NothingToCall));
+
+        betting.act(Action::Check).unwrap();
+        betting.start_round(0);
+        assert_eq!(betting.to_act(), Some(1));
+        assert_eq!(betting.act(Action::Raise(20)), Err(BetError::NothingToRaise));
+        assert_eq!(betting.act(Action::Bet(5)), Err(BetError::BelowMinimum(10)));
+    }
+
+    #[test]
+    fn test_min_raise() {
+        let mut betting: Betting = betting(&[1000, 1000, 1000], 0);
+        betting.act(Action::Raise(30)).unwrap();
+        // The raise was 20, so the next has to be to at least 50
+        assert_eq!(betting.min_raise_to(), 50);
+        assert_eq!(betting.act(Action::Raise(45)), Err(BetError::BelowMinimum(50)));
+        betting.act(Action::Raise(50)).unwrap();
+        betting.act(Action::Fold).unwrap();
+        assert_eq!(betting.to_act(), Some(0));
+        assert_eq!(betting.to_call(0), 20);
+    }
+
+    #[test]
+    fn test_short_all_in() {
+        let mut betting: Betting = betting(&[1000, 1000, 45], 0);
+        betting.act(Action::Raise(30)).unwrap();
+        betting.act(Action::Call).unwrap();
+        // All-in for 15 more isn't a full raise, so seat 0 can only call or fold
+        betting.act(Action::Raise(45)).unwrap();
+        assert_eq!(betting.to_act(), Some(0));
+        assert_eq!(betting.act(Action::Raise(100)), Err(BetError::RaiseNotAllowed));
+        betting.act(Action::Call).unwrap();
+        betting.act(Action::Call).unwrap();
+        assert_eq!(betting.to_act(), None);
+        assert!(betting.is_all_in(2));
+        assert_eq!(betting.pot(), 135);
+        assert_eq!(betting.pots(), vec![Pot { amount: 135, eligible: vec![0, 1, 2] }]);
+    }
+
+    #[test]
+    fn test_all_in_closes_betting() {
+        let mut betting: Betting = betting(&[1000, 300], 0);
+        betting.act(Action::Raise(1000)).unwrap();
+        betting.act(Action::Call).unwrap();
+        assert!(betting.is_closed());
+        assert_eq!(betting.pots(), vec![Pot { amount: 600, eligible: vec![0, 1] }, Pot { amount: 700, eligible: vec![0] }]);
+        betting.return_uncalled();
+        assert_eq!(betting.pot(), 600);
+        assert_eq!(betting.stacks(), &[700, 0]);
+        betting.award(&[1]);
+        assert_eq!(betting.stacks(), &[700, 600]);
+    }
+
+    #[test]
+    fn test_fold_wins() {
+        let mut betting: Betting = betting(&[1000, 1000], 1);
+        betting.act(Action::Raise(40)).unwrap();
+        betting.act(Action::Fold).unwrap();
+        assert!(betting.is_closed());
+        assert_eq!(betting.in_hand(), vec![1]);
+        betting.return_uncalled();
+        betting.award(&betting.in_hand());
+        assert_eq!(betting.stacks(), &[990, 1010]);
+    }
+
+    #[test]
+    fn test_split_odd_chip() {
+        let mut betting: Betting = betting(&[1000, 1000, 1000], 0);
+        betting.act(Action::Call).unwrap();
+        betting.act(Action::Fold).unwrap();
+        betting.act(Action::Check).unwrap();
+        assert_eq!(betting.pot(), 25);
+        betting.award(&[2, 0]);
+        assert_eq!(betting.stacks(), &[1002, 995, 1003]);
+    }
+
+    #[test]
+    fn test_pot_limit() {
+        let mut betting: Betting = Betting::with_structure(vec![1000, 1000, 1000], BLINDS, Structure::PotLimit);
+        betting.start_hand(0);
+        // Calling 10 makes the pot 25, so the most is a raise of 25 on top of the call
+        assert_eq!(betting.max_raise_to(0), 35);
+        assert_eq!(betting.act(Action::Raise(40)), Err(BetError::AboveLimit(35)));
+        betting.act(Action::Raise(35)).unwrap();
+        // Small blind calls 30 into a pot of 50, then raises 80 more
+        assert_eq!(betting.max_raise_to(1), 115);
+        betting.act(Action::Call).unwrap();
+        betting.act(Action::Call).unwrap();
+
+        betting.start_round(0);
+        assert_eq!(betting.max_raise_to(1), 105);
+        betting.act(Action::Bet(50)).unwrap();
+        // Facing a bet of 50 into 105, calling makes 205 and the raise goes to 255
+        assert_eq!(betting.max_raise_to(2), 255);
+        betting.act(Action::Raise(255)).unwrap();
+        assert_eq!(betting.max_raise_to(0), 255 + 410 + 255);
+    }
+
+    #[test]
+    fn test_fixed_limit() {
+        let structure: Structure = Structure::FixedLimit { small_bet: 10, big_bet: 20, cap: 4 };
+        let mut betting: Betting = Betting::with_structure(vec![1000, 1000, 35], BLINDS, structure);
+        betting.start_hand(0);
+        assert_eq!((betting.min_raise_to(), betting.max_raise_to(0)), (20, 20));
+        assert_eq!(betting.act(Action::Raise(30)), Err(BetError::AboveLimit(20)));
+        betting.act(Action::Raise(20)).unwrap();
+        betting.act(Action::Raise(30)).unwrap();
+        // A short all-in is the only raise that can be less than a full bet, and as an
+        // incomplete raise it doesn't count toward the cap
+        betting.act(Action::Raise(35)).unwrap();
+        assert!(betting.can_raise(0));
+        assert_eq!(betting.max_raise_to(0), 45);
+        betting.act(Action::Raise(45)).unwrap();
+        // The blind and three full raises cap the betting
+        assert!(!betting.can_raise(1));
+        assert_eq!(betting.act(Action::Raise(55)), Err(BetError::RaisesCapped));
+        betting.act(Action::Call).unwrap();
+        assert_eq!(betting.to_act(), None);
+
+        betting.start_round(0);
+        assert_eq!(betting.min_raise_to(), 10);
+        betting.start_round(0);
+        // Bets double on the turn
+        assert_eq!(betting.min_raise_to(), 20);
+        assert_eq!(betting.act(Action::Bet(10)), Err(BetError::BelowMinimum(20)));
+        betting.act(Action::Bet(20)).unwrap();
+        assert_eq!(betting.max_raise_to(0), 40);
+    }
+
+    #[test]
+    fn test_busted_seat_sits_out() {
+        let betting: Betting = betting(&[1000, 0, 1000, 1000], 0);
+        assert!(betting.has_folded(1));
+        assert_eq!(betting.stacks(), &[1000, 0, 995, 990]);
+        assert_eq!(betting.to_act(), Some(0));
+    }
+
+    #[test]
+    fn test_bring_in() {
+        let blinds: Blinds = Blinds { small: 5, big: 10, ante: 1 };
+        let mut betting: Betting = Betting::new(vec![1000, 1000, 1000], blinds);
+        betting.start_bring_in(1);
+        assert_eq!(betting.pot(), 8);
+        assert_eq!(betting.to_act(), Some(2));
+        // Completing to a full bet is the smallest raise, and the raise after it is a full bet
+        assert_eq!(betting.min_raise_to(), 10);
+        betting.act(Action::Call).unwrap();
+        betting.act(Action::Raise(10)).unwrap();
+        assert_eq!(betting.to_act(), Some(1));
+        assert_eq!(betting.min_raise_to(), 20);
+        betting.act(Action::Call).unwrap();
+        betting.act(Action::Call).unwrap();
+        assert_eq!(betting.to_act(), None);
+        assert_eq!(betting.pot(), 33);
+
+        // Once everyone calls the bring-in, it doesn't get to act again
+        betting.start_bring_in(0);
+        betting.act(Action::Call).unwrap();
+        betting.act(Action::Call).unwrap();
+        assert_eq!(betting.to_act(), None);
+    }
+}
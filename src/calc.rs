@@ -1,9 +1,24 @@
+pub mod chart;
+pub mod describe;
+pub mod enumerate;
+pub mod equity;
+pub mod lookup;
+pub mod low;
+pub mod nuts;
+pub mod omaha;
+pub mod outs;
+pub mod range;
+pub mod short_deck;
+pub mod texture;
+pub mod wild;
+
+use crate::calc::wild::{substitute, Wild};
 use crate::calc::Rank::*;
-use crate::card::{Card, Name, Name::*, Suit};
-use std::collections::{BTreeSet, HashMap, HashSet};
+use crate::card::{Card, CardSet, Name, Name::*, Suit, SUITS};
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Rank {
     HighCard,
     OnePair,
@@ -15,8 +30,16 @@ pub enum Rank {
     FourOfKind,
     StraightFlush,
     RoyalFlush,
+    // Only possible with wild cards
+    FiveOfKind,
 }
 
+pub const RANKS: [Rank; 11] = [HighCard, OnePair, TwoPair, ThreeOfKind, Straight, Flush, FullHouse, FourOfKind,
+                               StraightFlush, RoyalFlush, FiveOfKind];
+
+// Ranks Ten through Ace of a suit
+const ROYAL_FLUSH: u16 = 0x1F00;
+
 impl Display for Rank {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let rank_str = match self {
@@ -28,15 +51,43 @@ impl Display for Rank {
             Flush => "Flush",
             FullHouse => "Full House",
             FourOfKind => "Four of a Kind",
-            StraightFlush => "Straight",
-            RoyalFlush => "Royal Flush"
+            StraightFlush => "Straight Flush",
+            RoyalFlush => "Royal Flush",
+            FiveOfKind => "Five of a Kind"
         };
         write!(f, "{}", rank_str)
     }
 }
 
-// Calculates the best hand according to the calculated rank
+// The full value of a hand: its rank followed by the names of its best cards in
+// order of importance, so comparing two values settles every tie
+#[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct HandValue {
+    pub rank: Rank,
+    pub kickers: Vec<Name>,
+}
+
+// Evaluates a hand into a value that can be compared against any other hand
+pub fn evaluate(hand: &[Card]) -> HandValue {
+    let rank: Rank = calc_rank(hand);
+    let kickers: Vec<Name> = calc_best_hand(hand, rank).iter()
+        .map(|card| card.0)
+        .collect();
+    HandValue { rank, kickers }
+}
+
+// Evaluates a set of cards
+pub fn evaluate_set(cards: CardSet) -> HandValue {
+    evaluate(&Vec::from(cards))
+}
+
+// Calculates the best hand according to the calculated rank. Jokers are wild, and each one
+// that plays shows up as the card it became
 pub fn calc_best_hand(hand: &[Card], rank: Rank) -> Vec<Card> {
+    if has_joker(hand) {
+        let (_, cards, _): (HandValue, Vec<Card>, Vec<(Card, Card)>) = substitute(hand, Wild::Jokers);
+        return calc_best_hand(&cards, rank);
+    }
     let mut cards: Vec<Card> = hand.to_vec();
     let mut value_count: HashMap<Name, u8> = HashMap::new();
 
@@ -46,17 +97,17 @@ pub fn calc_best_hand(hand: &[Card], rank: Rank) -> Vec<Card> {
     // Sort cards according to rank
     match rank {
         HighCard | Flush | StraightFlush | RoyalFlush => {
-            cards.sort_by(|a, b| b.cmp(a));
+            cards.sort_by(|a, b| b.cmp_name(a));
         }
-        OnePair | TwoPair | ThreeOfKind | FullHouse | FourOfKind => {
+        OnePair | TwoPair | ThreeOfKind | FullHouse | FourOfKind | FiveOfKind => {
             cards.sort_by(|a, b| value_count[&b.0].cmp(&value_count[&a.0])
-                .then_with(|| b.cmp(a))
+                .then_with(|| b.cmp_name(a))
                 .then_with(|| a.1.cmp(&b.1)));
         }
         Straight => {
-            cards.sort_by(|a, b| a.cmp(b)
+            cards.sort_by(|a, b| a.cmp_name(b)
                 .then_with(|| a.1.cmp(&b.1)));
-            cards.dedup();
+            cards.dedup_by(|a, b| a.eq_name(b));
             cards.reverse();
         }
     }
@@ -64,14 +115,12 @@ pub fn calc_best_hand(hand: &[Card], rank: Rank) -> Vec<Card> {
     // Get most frequent suit if hand is a type of Flush
     if rank == Flush || rank == StraightFlush || rank == RoyalFlush {
         let suit: Suit = mode_suit(&cards).unwrap();
-        cards = cards.into_iter()
-            .filter(|c| c.1 == suit)
-            .collect();
+        cards.retain(|c| c.1 == suit);
     }
 
     // Get best hand depending on rank
     match rank {
-        HighCard | OnePair | ThreeOfKind | Flush | RoyalFlush =>
+        HighCard | OnePair | ThreeOfKind | Flush | RoyalFlush | FiveOfKind =>
             cards.into_iter()
                 .take(5)
                 .collect(),
@@ -80,7 +129,7 @@ pub fn calc_best_hand(hand: &[Card], rank: Rank) -> Vec<Card> {
             let mut kicker: Vec<Card> = cards.into_iter()
                 .skip(4)
                 .collect();
-            kicker.sort_by(|a, b| b.cmp(a));
+            kicker.sort_by(|a, b| b.cmp_name(a));
             if !kicker.is_empty() {
                 best_hand.push(kicker[0]);
             }
@@ -95,7 +144,7 @@ pub fn calc_best_hand(hand: &[Card], rank: Rank) -> Vec<Card> {
                 .filter(|straight| straight[0].0 as u8 - straight[4].0 as u8 == 4)
                 .take(1)
                 .flatten()
-                .map(|&c| c)
+                .copied()
                 .collect()
         }
         FullHouse => {
@@ -105,7 +154,7 @@ pub fn calc_best_hand(hand: &[Card], rank: Rank) -> Vec<Card> {
                 .filter(|&c| value_count[&c.0] >= 2)
                 .collect();
 
-            pairs.sort_by(|a, b| b.cmp(a)
+            pairs.sort_by(|a, b| b.cmp_name(a)
                 .then_with(|| a.1.cmp(&b.1)));
             best_hand.extend(&pairs[..2]);
             best_hand
@@ -115,66 +164,51 @@ pub fn calc_best_hand(hand: &[Card], rank: Rank) -> Vec<Card> {
 
 // Calculates the rank in a given array(hand) of cards
 pub fn calc_rank(hand: &[Card]) -> Rank {
-    if let Some(rank) = check_flush(&hand) {
+    if has_joker(hand) {
+        substitute(hand, Wild::Jokers).0.rank
+    } else if let Some(rank) = check_flush(hand) {
         rank
-    } else if is_straight(&hand) {
+    } else if is_straight(hand) {
         Straight
     } else {
-        other_rank(&hand)
+        other_rank(hand)
     }
 }
 
 // Checks if ahd is a flush, if so, what type of flush
-fn check_flush(hand: &[Card]) -> Option<Rank> {
-    let some_suit: Option<Suit> = mode_suit(&hand);
-
-    if some_suit == None || hand.len() < 5 {
-        return None;
-    }
-
-    let suit: Suit = some_suit.unwrap();
-    let flush: Vec<Card> = hand.iter()
-        .filter(|&c| c.1 == suit)
-        .map(|&c| c)
-        .collect();
-
-    if flush.len() < 5 {
-        return None;
-    }
-
-    if !is_straight(&flush) {
-        return Some(Flush);
-    }
-
-    let names: HashSet<Name> = hand.iter()
-        .map(|card| card.0)
-        .collect();
-    let royal_flush: HashSet<Name> = HashSet::from([Ten, Jack, Queen, King, AceHigh]);
-
-    match royal_flush.is_subset(&names) {
-        true => Some(RoyalFlush),
-        false => Some(StraightFlush)
+pub(crate) fn check_flush(hand: &[Card]) -> Option<Rank> {
+    let cards: CardSet = CardSet::from(hand);
+    let flush: u16 = SUITS.iter()
+        .map(|&suit| cards.suit_mask(suit))
+        .max_by_key(|mask| mask.count_ones())
+        .filter(|mask| mask.count_ones() >= 5)?;
+
+    if !has_straight(flush) {
+        Some(Flush)
+    } else if flush & ROYAL_FLUSH == ROYAL_FLUSH {
+        Some(RoyalFlush)
+    } else {
+        Some(StraightFlush)
     }
 }
 
 // Checks if hand is a straight
 fn is_straight(hand: &[Card]) -> bool {
-    if hand.len() < 5 {
-        return false;
-    }
+    has_straight(CardSet::from(hand).rank_mask())
+}
 
-    let mut names: BTreeSet<Name> = hand.iter()
-        .map(|card| card.0)
-        .collect();
+fn has_joker(hand: &[Card]) -> bool {
+    hand.iter().any(|card| card.0 == Joker)
+}
 
-    if names.contains(&AceHigh) {
-        names.insert(AceLow);
-    }
+// Checks for five ranks in a row in a rank mask, with the Ace also played low
+pub(crate) fn has_straight(mask: u16) -> bool {
+    has_straight_ace_high(mask << 1 | mask >> 12)
+}
 
-    names.into_iter()
-        .collect::<Vec<Name>>()
-        .windows(5)
-        .any(|straight| straight[4] as u8 - straight[0] as u8 == 4)
+// Checks for five ranks in a row in a rank mask, with the Ace only played high
+pub(crate) fn has_straight_ace_high(mask: u16) -> bool {
+    mask & mask >> 1 & mask >> 2 & mask >> 3 & mask >> 4 != 0
 }
 
 // Finds the most frequent suit in the array of cards
@@ -215,8 +249,10 @@ fn other_rank(hand: &[Card]) -> Rank {
 
 #[cfg(test)]
 mod calc_tests {
-    use crate::calc::{calc_rank, Rank::*, calc_best_hand};
-    use crate::card::{Card, Name::*, Suit::*};
+    use crate::calc::{calc_rank, evaluate, evaluate_set, HandValue, Rank::*, calc_best_hand};
+    use crate::calc::lookup::calc_strength;
+    use crate::card::{Card, CardSet, Name::*, Suit::*};
+    use std::collections::{HashMap, HashSet};
 
     #[test]
     fn test_rank() {
@@ -451,4 +487,171 @@ mod calc_tests {
                                                          Card(Eight, Diamonds),
                                                          Card(Six, Clubs)]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_evaluate() {
+        let hand: Vec<Card> = vec![Card(Nine, Hearts),
+                                   Card(Two, Diamonds),
+                                   Card(Nine, Spades),
+                                   Card(Eight, Diamonds),
+                                   Card(Two, Hearts),
+                                   Card(Six, Clubs),
+                                   Card(Four, Clubs)];
+        assert_eq!(evaluate(&hand), HandValue { rank: TwoPair,
+                                                kickers: vec![Nine, Nine, Two, Two, Eight] });
+    }
+
+    #[test]
+    fn test_hand_value_cmp() {
+        let aces_king: Vec<Card> = vec![Card(AceHigh, Hearts),
+                                        Card(AceHigh, Diamonds),
+                                        Card(King, Spades),
+                                        Card(Eight, Diamonds),
+                                        Card(Two, Hearts)];
+        let aces_queen: Vec<Card> = vec![Card(AceHigh, Spades),
+                                         Card(AceHigh, Clubs),
+                                         Card(Queen, Spades),
+                                         Card(Eight, Clubs),
+                                         Card(Two, Spades)];
+        let wheel: Vec<Card> = vec![Card(AceHigh, Hearts),
+                                    Card(Two, Diamonds),
+                                    Card(Three, Spades),
+                                    Card(Four, Diamonds),
+                                    Card(Five, Hearts)];
+        let six_high: Vec<Card> = vec![Card(Six, Clubs),
+                                       Card(Two, Clubs),
+                                       Card(Three, Hearts),
+                                       Card(Four, Spades),
+                                       Card(Five, Diamonds)];
+        assert!(evaluate(&aces_king) > evaluate(&aces_queen));
+        assert!(evaluate(&six_high) > evaluate(&wheel));
+        assert!(evaluate(&wheel) > evaluate(&aces_king));
+    }
+
+    #[test]
+    fn test_hand_value_sort() {
+        let hands: Vec<Vec<Card>> = vec![
+            vec![Card(Nine, Hearts), Card(Nine, Spades), Card(Nine, Diamonds), Card(Two, Hearts), Card(Two, Clubs)],
+            vec![Card(King, Hearts), Card(Jack, Hearts), Card(Eight, Hearts), Card(Four, Hearts), Card(Two, Hearts)],
+            vec![Card(King, Spades), Card(Jack, Spades), Card(Eight, Spades), Card(Four, Spades), Card(Three, Spades)],
+            vec![Card(AceHigh, Clubs), Card(King, Diamonds), Card(Eight, Clubs), Card(Four, Clubs), Card(Two, Clubs)],
+        ];
+        let mut values: Vec<HandValue> = hands.iter()
+            .map(|hand| evaluate(hand))
+            .collect();
+        values.sort();
+        assert_eq!(values.iter().map(|value| value.rank).collect::<Vec<_>>(),
+                   vec![HighCard, Flush, Flush, FullHouse]);
+        assert_eq!(values[1].kickers, vec![King, Jack, Eight, Four, Two]);
+        assert_eq!(values[2].kickers, vec![King, Jack, Eight, Four, Three]);
+    }
+
+    #[test]
+    fn test_hand_value_hash() {
+        let hand1: Vec<Card> = vec![Card(Ten, Hearts),
+                                    Card(Ten, Diamonds),
+                                    Card(Seven, Spades),
+                                    Card(Five, Diamonds),
+                                    Card(Three, Hearts)];
+        let hand2: Vec<Card> = vec![Card(Ten, Spades),
+                                    Card(Ten, Clubs),
+                                    Card(Seven, Hearts),
+                                    Card(Five, Clubs),
+                                    Card(Three, Spades)];
+        let values: HashSet<HandValue> = vec![evaluate(&hand1), evaluate(&hand2)].into_iter().collect();
+        assert_eq!(values.len(), 1);
+
+        let mut wins: HashMap<HandValue, u8> = HashMap::new();
+        *wins.entry(evaluate(&hand1)).or_insert(0) += 1;
+        *wins.entry(evaluate(&hand2)).or_insert(0) += 1;
+        assert_eq!(wins[&evaluate(&hand1)], 2);
+    }
+
+    #[test]
+    fn test_royal_flush_needs_suited_ace() {
+        let hand: Vec<Card> = vec![Card(Nine, Hearts),
+                                   Card(King, Hearts),
+                                   Card(Queen, Hearts),
+                                   Card(Jack, Hearts),
+                                   Card(Ten, Hearts),
+                                   Card(AceHigh, Spades),
+                                   Card(Two, Clubs)];
+        assert_eq!(calc_rank(&hand), StraightFlush);
+    }
+
+    #[test]
+    fn test_evaluate_set() {
+        let hand: Vec<Card> = vec![Card(Four, Diamonds),
+                                   Card(AceHigh, Clubs),
+                                   Card(Two, Diamonds),
+                                   Card(Queen, Hearts),
+                                   Card(Five, Spades),
+                                   Card(Jack, Hearts),
+                                   Card(Three, Hearts)];
+        assert_eq!(evaluate_set(CardSet::from(&hand)), evaluate(&hand));
+        assert_eq!(evaluate_set(CardSet::from(&hand)).kickers, vec![Five, Four, Three, Two, AceLow]);
+    }
+
+    #[test]
+    fn test_straight_with_pairs() {
+        let hand: Vec<Card> = vec![Card(Nine, Diamonds),
+                                   Card(Ten, Clubs),
+                                   Card(Ten, Diamonds),
+                                   Card(Jack, Hearts),
+                                   Card(Seven, Spades),
+                                   Card(Eight, Hearts),
+                                   Card(Jack, Clubs)];
+        assert_eq!(calc_rank(&hand), Straight);
+        assert_eq!(calc_best_hand(&hand, Straight), vec![Card(Jack, Hearts),
+                                                         Card(Ten, Diamonds),
+                                                         Card(Nine, Diamonds),
+                                                         Card(Eight, Hearts),
+                                                         Card(Seven, Spades)]);
+    }
+
+    #[test]
+    fn test_jokers() {
+        let hand: Vec<Card> = vec![Card(AceHigh, Hearts),
+                                   Card(King, Hearts),
+                                   Card(Queen, Hearts),
+                                   Card(Jack, Hearts),
+                                   Card(Joker, Spades),
+                                   Card(Two, Clubs),
+                                   Card(Three, Diamonds)];
+        assert_eq!(calc_rank(&hand), RoyalFlush);
+        // The joker shows up as the Ten it became
+        assert_eq!(calc_best_hand(&hand, RoyalFlush), vec![Card(AceHigh, Hearts),
+                                                           Card(King, Hearts),
+                                                           Card(Queen, Hearts),
+                                                           Card(Jack, Hearts),
+                                                           Card(Ten, Hearts)]);
+        assert_eq!(evaluate(&hand).kickers, vec![AceHigh, King, Queen, Jack, Ten]);
+    }
+
+    #[test]
+    fn test_four_card_jokers() {
+        let hand: Vec<Card> = vec![Card(AceHigh, Hearts),
+                                   Card(AceHigh, Spades),
+                                   Card(Seven, Clubs),
+                                   Card(Joker, Spades)];
+        let plain: Vec<Card> = vec![Card(AceHigh, Hearts),
+                                    Card(AceHigh, Spades),
+                                    Card(Seven, Clubs),
+                                    Card(AceHigh, Clubs)];
+        assert_eq!(calc_rank(&hand), ThreeOfKind);
+        assert_eq!(evaluate(&hand), evaluate(&plain));
+        // The rank asked for is respected once the joker is replaced
+        assert_eq!(calc_best_hand(&hand, HighCard), calc_best_hand(&plain, HighCard));
+    }
+
+    #[test]
+    #[should_panic(expected = "Jokers must be substituted first")]
+    fn test_joker_strength() {
+        let hand: Vec<Card> = vec![Card(AceHigh, Hearts),
+                                   Card(King, Hearts),
+                                   Card(Queen, Hearts),
+                                   Card(Jack, Hearts),
+                                   Card(Joker, Spades)];
+        calc_strength(&hand);
+    }
+}
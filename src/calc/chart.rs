@@ -0,0 +1,190 @@
+use crate::calc::equity::Equity;
+use crate::calc::range::{range_equity, HandClass, Range, Suitedness};
+use crate::card::{Name, Name::*, NAMES};
+use rand::Rng;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+// Preflop all-in equity for all 169 starting hands, laid out the usual way: rows and columns
+// run from Ace down to Two, pairs on the diagonal, suited hands above it and offsuit below
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chart {
+    pub opponents: usize,
+    pub trials: u64,
+    // Equity percentages indexed by row then column
+    pub grid: [[f64; 13]; 13],
+}
+
+impl Chart {
+    // Equity of a starting hand written like "AKs", "T9o" or "77"
+    pub fn equity(&self, hand: &str) -> Option<f64> {
+        let class: HandClass = hand.parse().ok()?;
+        let (row, column): (usize, usize) = match class.suitedness {
+            _ if class.high == class.low => (12 - class.high, 12 - class.high),
+            Suitedness::Suited => (12 - class.high, 12 - class.low),
+            Suitedness::Offsuit => (12 - class.low, 12 - class.high),
+            Suitedness::Any => return None,
+        };
+        Some(self.grid[row][column])
+    }
+
+    // Comma separated grid with a header row and column of names
+    pub fn to_csv(&self) -> String {
+        let mut csv: String = String::new();
+        for symbol in symbols() {
+            csv.push(',');
+            csv.push(symbol);
+        }
+        csv.push('\n');
+        for (row, symbol) in symbols().enumerate() {
+            csv.push(symbol);
+            for column in 0..13 {
+                csv.push_str(&format!(",{:.2}", self.grid[row][column]));
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+
+    // JSON object holding the settings and a 13x13 grid of hands and equities
+    pub fn to_json(&self) -> String {
+        let rows: Vec<String> = (0..13)
+            .map(|row| {
+                let cells: Vec<String> = (0..13)
+                    .map(|column| format!("{{\"hand\":\"{}\",\"equity\":{:.2}}}", label(row, column), self.grid[row][column]))
+                    .collect();
+                format!("[{}]", cells.join(","))
+            })
+            .collect();
+        format!("{{\"opponents\":{},\"trials\":{},\"grid\":[{}]}}", self.opponents, self.trials, rows.join(","))
+    }
+}
+
+// Text grid of hands and equity percentages
+impl Display for Chart {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in 0..13 {
+            let cells: Vec<String> = (0..13)
+                .map(|column| format!("{:<3} {:>4.1}", label(row, column), self.grid[row][column]))
+                .collect();
+            writeln!(f, "{}", cells.join("  "))?;
+        }
+        Ok(())
+    }
+}
+
+// Calculates each starting hand's equity against the given opponents, a single Range::any_two()
+// for heads-up against a random hand, by dealing random holdings and boards
+pub fn preflop_chart<R: Rng>(opponents: &[Range], trials: u64, rng: &mut R) -> Chart {
+    assert!(!opponents.is_empty(), "Error: A chart needs at least one opponent");
+    let mut grid: [[f64; 13]; 13] = [[0.0; 13]; 13];
+    for (row, cells) in grid.iter_mut().enumerate() {
+        for (column, cell) in cells.iter_mut().enumerate() {
+            let mut hand: Range = Range::new();
+            class(row, column).combos()
+                .into_iter()
+                .for_each(|combo| hand.add(combo, 1.0));
+            let mut ranges: Vec<Range> = vec![hand];
+            ranges.extend_from_slice(opponents);
+            let results: Vec<Equity> = range_equity(&ranges, &[], &[], trials, rng);
+            *cell = results[0].equity();
+        }
+    }
+    Chart { opponents: opponents.len(), trials, grid }
+}
+
+fn class(row: usize, column: usize) -> HandClass {
+    let (first, second): (usize, usize) = (12 - row, 12 - column);
+    HandClass {
+        high: first.max(second),
+        low: first.min(second),
+        suitedness: if row < column { Suitedness::Suited } else { Suitedness::Offsuit },
+    }
+}
+
+fn label(row: usize, column: usize) -> String {
+    let class: HandClass = class(row, column);
+    let (high, low): (char, char) = (symbol(NAMES[class.high]), symbol(NAMES[class.low]));
+    match row.cmp(&column) {
+        Ordering::Equal => format!("{}{}", high, low),
+        Ordering::Less => format!("{}{}s", high, low),
+        Ordering::Greater => format!("{}{}o", high, low),
+    }
+}
+
+// Names from Ace down to Two, as the rows and columns run
+fn symbols() -> impl Iterator<Item = char> {
+    NAMES.iter().rev().map(|&name| symbol(name))
+}
+
+// Single character for a name, Ten being T so every hand is two characters
+fn symbol(name: Name) -> char {
+    match name {
+        Ten => 'T',
+        _ => name.to_string().chars().next().unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod chart_tests {
+    use crate::calc::chart::{preflop_chart, Chart};
+    use crate::calc::range::Range;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    // Enough trials to pin each cell within about a percent
+    const TRIALS: u64 = 20_000;
+
+    fn chart(opponents: &[Range], trials: u64) -> Chart {
+        preflop_chart(opponents, trials, &mut StdRng::seed_from_u64(18))
+    }
+
+    // Checks chart cells against their well known equities
+    fn assert_cells(chart: &Chart, cells: &[(&str, f64)]) {
+        for &(hand, known) in cells {
+            let equity: f64 = chart.equity(hand).unwrap();
+            assert!((equity - known).abs() < 1.0, "{} has {:.2}% equity, expected {:.1}%", hand, equity, known);
+        }
+    }
+
+    #[test]
+    fn test_heads_up() {
+        let chart: Chart = chart(&[Range::any_two()], TRIALS);
+        assert_cells(&chart, &[("AA", 85.2), ("KK", 82.4), ("AKs", 67.0), ("AKo", 65.4), ("22", 50.3),
+                               ("T9s", 54.1), ("72o", 34.6)]);
+        assert_eq!(chart.grid[0][0], chart.equity("AA").unwrap());
+        assert_eq!(chart.equity("KAs"), chart.equity("AKs"));
+        assert_eq!(chart.equity("AKs"), Some(chart.grid[0][1]));
+        assert_eq!(chart.equity("AKo"), Some(chart.grid[1][0]));
+        assert_eq!(chart.equity("AKx"), None);
+        assert_eq!(chart.equity("AK"), None);
+    }
+
+    #[test]
+    fn test_multiway_and_range() {
+        let three_way: Chart = chart(&[Range::any_two(), Range::any_two()], TRIALS);
+        assert_eq!(three_way.opponents, 2);
+        assert_cells(&three_way, &[("AA", 73.4), ("KK", 68.9)]);
+        let against_kings: Chart = chart(&["KK".parse().unwrap()], TRIALS);
+        assert_cells(&against_kings, &[("QQ", 18.3), ("AA", 81.9)]);
+    }
+
+    #[test]
+    fn test_formats() {
+        let chart: Chart = chart(&[Range::any_two()], 300);
+        let csv: String = chart.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 14);
+        assert_eq!(lines[0], ",A,K,Q,J,T,9,8,7,6,5,4,3,2");
+        assert!(lines[1].starts_with(&format!("A,{:.2},", chart.grid[0][0])));
+        assert_eq!(lines[13].split(',').count(), 14);
+
+        let json: String = chart.to_json();
+        assert!(json.starts_with("{\"opponents\":1,\"trials\":300,\"grid\":[[{\"hand\":\"AA\""));
+        assert!(json.contains(&format!("{{\"hand\":\"AKo\",\"equity\":{:.2}}}", chart.grid[1][0])));
+        assert_eq!(json.matches("\"hand\"").count(), 169);
+
+        let text: String = chart.to_string();
+        assert_eq!(text.lines().count(), 13);
+        assert!(text.lines().last().unwrap().ends_with(&format!("22  {:>4.1}", chart.grid[12][12])));
+    }
+}
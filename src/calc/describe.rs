@@ -0,0 +1,175 @@
+use crate::calc::HandValue;
+use crate::calc::Rank::{self, *};
+use crate::card::{Name, Name::*};
+use std::cmp::Ordering;
+
+const ORDINALS: [&str; 5] = ["first", "second", "third", "fourth", "fifth"];
+
+// Describes a hand value in full, such as "Full house, Nines full of Twos"
+pub fn describe(value: &HandValue) -> String {
+    let kickers: &[Name] = &value.kickers;
+    match value.rank {
+        HighCard => format!("High card, {} high", word(kickers[0])),
+        OnePair => format!("One pair, {}{}", plural(kickers[0]), kicker(kickers.get(2))),
+        TwoPair => format!("Two pair, {} and {}{}", plural(kickers[0]), plural(kickers[2]), kicker(kickers.get(4))),
+        ThreeOfKind => format!("Three of a kind, {}", plural(kickers[0])),
+        Straight => format!("Straight, {} high", word(kickers[0])),
+        Flush => format!("Flush, {} high", word(kickers[0])),
+        FullHouse => format!("Full house, {} full of {}", plural(kickers[0]), plural(kickers[3])),
+        FourOfKind => format!("Four of a kind, {}{}", plural(kickers[0]), kicker(kickers.get(4))),
+        StraightFlush => format!("Straight flush, {} high", word(kickers[0])),
+        RoyalFlush => "Royal flush".to_string(),
+        FiveOfKind => format!("Five of a kind, {}", plural(kickers[0])),
+    }
+}
+
+// Explains how a hand value fares against another, such as "wins on second kicker (Q vs J)"
+pub fn explain(value: &HandValue, other: &HandValue) -> String {
+    let outcome: &str = match value.cmp(other) {
+        Ordering::Greater => "wins",
+        Ordering::Less => "loses",
+        Ordering::Equal => return "ties, both hands are the same".to_string(),
+    };
+
+    if value.rank != other.rank {
+        let (higher, lower): (Rank, Rank) = if value.rank > other.rank {
+            (value.rank, other.rank)
+        } else {
+            (other.rank, value.rank)
+        };
+        return format!("{}, {} beats {}", outcome, higher, lower);
+    }
+
+    let i: usize = value.kickers.iter()
+        .zip(&other.kickers)
+        .position(|(a, b)| a != b)
+        .unwrap_or(0);
+    let (mine, theirs): (Name, Name) = (value.kickers[i], other.kickers[i]);
+    let made: usize = made_cards(value.rank, false);
+    let reason: String = if i < made {
+        part(value.rank, i).to_string()
+    } else if made == 0 {
+        format!("{} card", ORDINALS[i])
+    } else {
+        format!("{} kicker", ORDINALS[i - made])
+    };
+    format!("{} on {} ({} vs {})", outcome, reason, mine, theirs)
+}
+
+// How many of the five cards make up the rank itself, the rest are kickers. A flush is made by
+// all five of its cards, but when whole flushes are compared it comes down to the cards one by
+// one, so `whole_flush` says which of the two is wanted
+pub(crate) fn made_cards(rank: Rank, whole_flush: bool) -> usize {
+    match rank {
+        Flush if whole_flush => 5,
+        HighCard | Flush => 0,
+        OnePair => 2,
+        ThreeOfKind => 3,
+        TwoPair | FourOfKind => 4,
+        Straight | FullHouse | StraightFlush | RoyalFlush | FiveOfKind => 5,
+    }
+}
+
+// Names the part of a made hand that the card at the given position belongs to
+fn part(rank: Rank, i: usize) -> &'static str {
+    match rank {
+        TwoPair if i < 2 => "top pair",
+        TwoPair => "second pair",
+        FullHouse if i < 3 => "three of a kind",
+        FullHouse => "pair",
+        OnePair => "pair",
+        ThreeOfKind => "three of a kind",
+        FourOfKind => "four of a kind",
+        FiveOfKind => "five of a kind",
+        _ => "high card",
+    }
+}
+
+fn kicker(name: Option<&Name>) -> String {
+    match name {
+        Some(&name) => format!(" with {} {} kicker", article(name), word(name)),
+        None => String::new(),
+    }
+}
+
+fn article(name: Name) -> &'static str {
+    match name {
+        AceLow | AceHigh | Eight => "an",
+        _ => "a",
+    }
+}
+
+fn word(name: Name) -> &'static str {
+    match name {
+        AceLow | AceHigh => "Ace",
+        Two => "Two",
+        Three => "Three",
+        Four => "Four",
+        Five => "Five",
+        Six => "Six",
+        Seven => "Seven",
+        Eight => "Eight",
+        Nine => "Nine",
+        Ten => "Ten",
+        Jack => "Jack",
+        Queen => "Queen",
+        King => "King",
+        Joker => "Joker",
+    }
+}
+
+fn plural(name: Name) -> String {
+    match name {
+        Six => "Sixes".to_string(),
+        _ => format!("{}s", word(name)),
+    }
+}
+
+#[cfg(test)]
+mod describe_tests {
+    use crate::calc::describe::{describe, explain, made_cards};
+    use crate::calc::{evaluate, HandValue, Rank::*};
+    use crate::card::parse_cards;
+
+    fn value(text: &str) -> HandValue {
+        evaluate(&parse_cards(text).unwrap())
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(describe(&value("9h 9d 9s 2c 2h")), "Full house, Nines full of Twos");
+        assert_eq!(describe(&value("Ah Ad 8s 8c Kh 3d 2c")), "Two pair, Aces and Eights with a King kicker");
+        assert_eq!(describe(&value("Kh 9h 7h 4h 2h")), "Flush, King high");
+        assert_eq!(describe(&value("6h 6d Qs 8c 2h")), "One pair, Sixes with a Queen kicker");
+        assert_eq!(describe(&value("Ah 2d 3s 4c 5h")), "Straight, Five high");
+        assert_eq!(describe(&value("Ah Kd Js 4c 5h")), "High card, Ace high");
+        assert_eq!(describe(&value("9h 8h 7h 6h 5h")), "Straight flush, Nine high");
+        assert_eq!(describe(&value("Ah Kh Qh Jh Th")), "Royal flush");
+        assert_eq!(describe(&value("7h 7d 7s 7c Ah")), "Four of a kind, Sevens with an Ace kicker");
+    }
+
+    #[test]
+    fn test_explain_kickers() {
+        let (winner, loser): (HandValue, HandValue) = (value("Ah Ad Ks Qc 2h"), value("As Ac Kd Jc 9h"));
+        assert_eq!(explain(&winner, &loser), "wins on second kicker (Q vs J)");
+        assert_eq!(explain(&loser, &winner), "loses on second kicker (J vs Q)");
+        assert_eq!(explain(&value("Kh Qd 9s 7c 2h"), &value("Ks Qc 8d 7h 2d")), "wins on third card (9 vs 8)");
+    }
+
+    #[test]
+    fn test_explain_made_hands() {
+        assert_eq!(explain(&value("9h 9d 9s 2c 2h"), &value("9h 9d 9s 3c 3h")), "loses on pair (2 vs 3)");
+        assert_eq!(explain(&value("Ah Ad 8s 8c Kh"), &value("As Ac 7s 7c Kd")), "wins on second pair (8 vs 7)");
+        assert_eq!(explain(&value("Kh 9h 7h 4h 2h"), &value("9h 8d 7s 6c 5h")), "wins, Flush beats Straight");
+        assert_eq!(explain(&value("9h 8d 7s 6c 5h"), &value("Kh 9h 7h 4h 2h")), "loses, Flush beats Straight");
+        assert_eq!(explain(&value("Kh Qd 9s 7c 2h"), &value("Ks Qc 9d 7h 2d")), "ties, both hands are the same");
+    }
+
+    #[test]
+    fn test_made_cards() {
+        assert_eq!(made_cards(TwoPair, false), 4);
+        assert_eq!(made_cards(Flush, false), 0);
+        assert_eq!(made_cards(Flush, true), 5);
+        assert_eq!(made_cards(HighCard, true), 0);
+    }
+}
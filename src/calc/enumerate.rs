@@ -0,0 +1,162 @@
+use crate::calc::lookup::{calc_set_strength, strength_rank};
+use crate::calc::Rank::{self, *};
+use crate::calc::RANKS;
+use crate::card::{Card, CardSet};
+use std::collections::BTreeMap;
+
+// Number of five card hands of each rank in a 52 card deck
+pub const FIVE_CARD_COUNTS: [(Rank, u64); 10] = [(HighCard, 1_302_540),
+                                                 (OnePair, 1_098_240),
+                                                 (TwoPair, 123_552),
+                                                 (ThreeOfKind, 54_912),
+                                                 (Straight, 10_200),
+                                                 (Flush, 5_108),
+                                                 (FullHouse, 3_744),
+                                                 (FourOfKind, 624),
+                                                 (StraightFlush, 36),
+                                                 (RoyalFlush, 4)];
+
+// Number of seven card hands whose best five cards make each rank
+pub const SEVEN_CARD_COUNTS: [(Rank, u64); 10] = [(HighCard, 23_294_460),
+                                                  (OnePair, 58_627_800),
+                                                  (TwoPair, 31_433_400),
+                                                  (ThreeOfKind, 6_461_620),
+                                                  (Straight, 6_180_020),
+                                                  (Flush, 4_047_644),
+                                                  (FullHouse, 3_473_184),
+                                                  (FourOfKind, 224_848),
+                                                  (StraightFlush, 37_260),
+                                                  (RoyalFlush, 4_324)];
+
+// Walks every way of choosing k cards in standard order, the chosen positions counting up
+// like digits with the rightmost moving fastest
+pub struct Combinations {
+    // Bit of each card to choose from in the set
+    bits: Vec<u64>,
+    positions: Vec<usize>,
+    done: bool,
+}
+
+impl Iterator for Combinations {
+    type Item = CardSet;
+
+    fn next(&mut self) -> Option<CardSet> {
+        if self.done {
+            return None;
+        }
+        let combo: CardSet = CardSet::from_bits(self.positions.iter().fold(0, |bits, &i| bits | self.bits[i]));
+
+        // Move the rightmost position that still has room, and line the rest up behind it
+        let (n, k): (usize, usize) = (self.bits.len(), self.positions.len());
+        match (0..k).rev().find(|&i| self.positions[i] < n - k + i) {
+            Some(i) => {
+                self.positions[i] += 1;
+                for j in (i + 1)..k {
+                    self.positions[j] = self.positions[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+        Some(combo)
+    }
+}
+
+// Every way of choosing k of the given cards
+pub fn combinations(cards: &[Card], k: usize) -> Combinations {
+    let bits: Vec<u64> = cards.iter()
+        .map(|&card| CardSet::from(card).bits())
+        .collect();
+    Combinations { bits, positions: (0..k).collect(), done: k > cards.len() }
+}
+
+// Every hand of k cards dealt from a full deck
+pub fn all_hands(k: usize) -> Combinations {
+    combinations(&Vec::from(CardSet::DECK), k)
+}
+
+// Counts how many hands of each rank an evaluator finds among every hand of the given size
+pub fn tally_ranks(hand_size: usize, evaluator: fn(CardSet) -> Rank) -> BTreeMap<Rank, u64> {
+    let mut counts: [u64; 11] = [0; 11];
+    all_hands(hand_size).for_each(|hand| counts[evaluator(hand) as usize] += 1);
+    RANKS.iter()
+        .zip(counts)
+        .filter(|&(_, count)| count > 0)
+        .map(|(&rank, count)| (rank, count))
+        .collect()
+}
+
+// Checks an evaluator against the known five or seven card totals, returning each rank it
+// miscounts along with the expected and found counts
+pub fn verify_ranks(hand_size: usize, evaluator: fn(CardSet) -> Rank) -> Result<(), Vec<(Rank, u64, u64)>> {
+    let expected: &[(Rank, u64)] = match hand_size {
+        5 => &FIVE_CARD_COUNTS,
+        7 => &SEVEN_CARD_COUNTS,
+        _ => panic!("Error: Known totals are for 5 or 7 card hands"),
+    };
+    let counts: BTreeMap<Rank, u64> = tally_ranks(hand_size, evaluator);
+    let wrong: Vec<(Rank, u64, u64)> = expected.iter()
+        .map(|&(rank, count)| (rank, count, counts.get(&rank).copied().unwrap_or(0)))
+        .filter(|&(_, count, found)| count != found)
+        .collect();
+    if wrong.is_empty() { Ok(()) } else { Err(wrong) }
+}
+
+// Ranks a hand with the lookup table evaluator
+pub fn lookup_rank(cards: CardSet) -> Rank {
+    strength_rank(calc_set_strength(cards))
+}
+
+#[cfg(test)]
+mod enumerate_tests {
+    use crate::calc::enumerate::{all_hands, combinations, lookup_rank, verify_ranks};
+    use crate::calc::{evaluate_set, Rank};
+    use crate::card::{parse_cards, Card, CardSet};
+
+    #[test]
+    fn test_combinations() {
+        let cards: Vec<Card> = parse_cards("2h 3h 4h 5h").unwrap();
+        let combos: Vec<CardSet> = combinations(&cards, 2).collect();
+        assert_eq!(combos.len(), 6);
+        assert_eq!(combos[0], "2h 3h".parse().unwrap());
+        assert_eq!(combos[1], "2h 4h".parse().unwrap());
+        assert_eq!(combos[5], "4h 5h".parse().unwrap());
+        assert_eq!(combinations(&cards, 0).count(), 1);
+        assert_eq!(combinations(&cards, 4).count(), 1);
+        assert_eq!(combinations(&cards, 5).count(), 0);
+    }
+
+    #[test]
+    fn test_all_hands() {
+        assert_eq!(all_hands(2).count(), 1_326);
+        assert_eq!(all_hands(5).count(), 2_598_960);
+    }
+
+    #[test]
+    fn test_five_card_lookup() {
+        assert_eq!(verify_ranks(5, lookup_rank), Ok(()));
+    }
+
+    #[test]
+    fn test_five_card_evaluate() {
+        assert_eq!(verify_ranks(5, |cards| evaluate_set(cards).rank), Ok(()));
+    }
+
+    #[test]
+    fn test_wrong_evaluator() {
+        let wrong: Vec<(Rank, u64, u64)> = verify_ranks(5, |_| Rank::HighCard).unwrap_err();
+        assert_eq!(wrong.len(), 10);
+        assert_eq!(wrong[0], (Rank::HighCard, 1_302_540, 2_598_960));
+    }
+
+    #[test]
+    fn test_seven_card_lookup() {
+        assert_eq!(verify_ranks(7, lookup_rank), Ok(()));
+    }
+
+    // The slower evaluator takes a few minutes over every seven card hand, run with --ignored
+    #[test]
+    #[ignore]
+    fn test_seven_card_evaluate() {
+        assert_eq!(verify_ranks(7, |cards| evaluate_set(cards).rank), Ok(()));
+    }
+}
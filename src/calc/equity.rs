@@ -0,0 +1,401 @@
+use crate::calc::enumerate::combinations;
+use crate::calc::lookup::calc_set_strength;
+use crate::card::{Card, CardSet};
+use rand::Rng;
+use std::ops::AddAssign;
+use std::thread;
+
+// How one player fared over a number of run-outs
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Equity {
+    pub wins: u64,
+    pub ties: u64,
+    pub losses: u64,
+    // Pots won, where a pot split n ways counts as 1/n
+    pub share: f64,
+    pub trials: u64,
+}
+
+impl Equity {
+    pub fn win_pct(&self) -> f64 {
+        self.percent(self.wins as f64)
+    }
+
+    pub fn tie_pct(&self) -> f64 {
+        self.percent(self.ties as f64)
+    }
+
+    pub fn lose_pct(&self) -> f64 {
+        self.percent(self.losses as f64)
+    }
+
+    // Percentage of the pot the player can expect to win
+    pub fn equity(&self) -> f64 {
+        self.percent(self.share)
+    }
+
+    fn percent(&self, amount: f64) -> f64 {
+        match self.trials {
+            0 => 0.0,
+            trials => 100.0 * amount / trials as f64,
+        }
+    }
+}
+
+impl AddAssign for Equity {
+    fn add_assign(&mut self, other: Equity) {
+        self.wins += other.wins;
+        self.ties += other.ties;
+        self.losses += other.losses;
+        self.share += other.share;
+        self.trials += other.trials;
+    }
+}
+
+// Estimates Texas Hold'em equity by dealing random run-outs of the board
+pub fn monte_carlo<R: Rng>(holes: &[[Card; 2]],
+                           board: &[Card],
+                           dead: &[Card],
+                           trials: u64,
+                           rng: &mut R) -> Vec<Equity> {
+    let hands: Vec<CardSet> = holes.iter()
+        .map(|hole| CardSet::from(&hole[..]))
+        .collect();
+    let deck: CardSet = remaining_deck(&hands, CardSet::from(board), dead);
+    sample(hands.len(), board, deck, trials, rng, |dealt, _| {
+        dealt.copy_from_slice(&hands);
+        CardSet::EMPTY
+    })
+}
+
+// Plays out random trials, each asking deal for every player's hand along with the cards
+// it took out of the deck, then dealing the rest of the board from what's left
+pub(crate) fn sample<R: Rng, F>(players: usize,
+                                board: &[Card],
+                                deck: CardSet,
+                                trials: u64,
+                                rng: &mut R,
+                                mut deal: F) -> Vec<Equity>
+    where F: FnMut(&mut [CardSet], &mut R) -> CardSet {
+    assert!(board.len() <= 5, "Error: Board can't have more than 5 cards");
+    let board_set: CardSet = CardSet::from(board);
+    let mut deck: Vec<Card> = deck.into();
+    let needed: usize = 5 - board.len();
+    let mut results: Vec<Equity> = vec![Equity::default(); players];
+    let mut hands: Vec<CardSet> = vec![CardSet::new(); players];
+    let mut strengths: Vec<u16> = vec![0; players];
+
+    for _ in 0..trials {
+        let used: CardSet = deal(&mut hands, rng);
+        // Partial shuffle, only the cards being dealt need to be random, and any card a hand
+        // already holds is picked again
+        for i in 0..needed {
+            let j: usize = loop {
+                let j: usize = rng.gen_range(i..deck.len());
+                if !used.contains(deck[j]) {
+                    break j;
+                }
+            };
+            deck.swap(i, j);
+        }
+        let run_out: CardSet = board_set | CardSet::from(&deck[..needed]);
+        for (strength, &hand) in strengths.iter_mut().zip(&hands) {
+            *strength = calc_set_strength(run_out | hand);
+        }
+        showdown(&strengths, &mut results);
+    }
+    results
+}
+
+// Calculates exact Texas Hold'em equity over every board, an empty hand is an unknown opponent
+pub fn exact_holdem(hands: &[Vec<Card>], board: &[Card], dead: &[Card]) -> Vec<Equity> {
+    exact(hands, 2, board, 5, dead)
+}
+
+// Calculates exact 7 Card Stud equity over every way the hands can be finished
+pub fn exact_stud(hands: &[Vec<Card>], dead: &[Card]) -> Vec<Equity> {
+    exact(hands, 7, &[], 0, dead)
+}
+
+// Calculates exact equity by dealing out every combination of the unknown cards, each
+// player ends with hand_size cards of their own plus a shared board of board_size cards
+pub fn exact(hands: &[Vec<Card>],
+             hand_size: usize,
+             board: &[Card],
+             board_size: usize,
+             dead: &[Card]) -> Vec<Equity> {
+    assert!(board.len() <= board_size, "Error: Board has too many cards");
+    assert!(hands.iter().all(|hand| hand.len() <= hand_size), "Error: Hand has too many cards");
+    let known: Vec<CardSet> = hands.iter()
+        .map(CardSet::from)
+        .collect();
+    let deck: CardSet = remaining_deck(&known, CardSet::from(board), dead);
+    let table: Table = Table { board: CardSet::from(board), hands: known };
+
+    // Deal the board first, then each player in turn
+    let mut stages: Vec<(Stage, usize)> = vec![(Stage::Board, board_size - board.len())];
+    stages.extend(hands.iter()
+        .enumerate()
+        .map(|(i, hand)| (Stage::Hand(i), hand_size - hand.len())));
+    stages.retain(|&(_, count)| count > 0);
+
+    let mut results: Vec<Equity> = vec![Equity::default(); hands.len()];
+    if stages.is_empty() {
+        table.score(&mut results);
+        return results;
+    }
+
+    // Split the first stage between threads and let each deal out the rest
+    let (stage, count): (Stage, usize) = stages[0];
+    let firsts: Vec<CardSet> = combinations(&Vec::from(deck), count).collect();
+    if firsts.is_empty() {
+        return results;
+    }
+    let threads: usize = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk: usize = firsts.len().div_ceil(threads);
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = firsts.chunks(chunk)
+            .map(|firsts| {
+                let mut table: Table = table.clone();
+                let stages: &[(Stage, usize)] = &stages[1..];
+                scope.spawn(move || {
+                    let mut results: Vec<Equity> = vec![Equity::default(); table.hands.len()];
+                    for &cards in firsts {
+                        table.add(stage, cards);
+                        table.deal(stages, deck - cards, &mut results);
+                        table.remove(stage, cards);
+                    }
+                    results
+                })
+            })
+            .collect();
+        for worker in workers {
+            let partial: Vec<Equity> = worker.join().expect("Error: Equity thread panicked");
+            results.iter_mut()
+                .zip(partial)
+                .for_each(|(result, partial)| *result += partial);
+        }
+    });
+    results
+}
+
+#[derive(Clone, Copy)]
+enum Stage {
+    Board,
+    Hand(usize),
+}
+
+// The cards in play during an exact enumeration
+#[derive(Clone)]
+struct Table {
+    board: CardSet,
+    hands: Vec<CardSet>,
+}
+
+impl Table {
+    fn add(&mut self, stage: Stage, cards: CardSet) {
+        match stage {
+            Stage::Board => self.board = self.board | cards,
+            Stage::Hand(i) => self.hands[i] = self.hands[i] | cards,
+        }
+    }
+
+    fn remove(&mut self, stage: Stage, cards: CardSet) {
+        match stage {
+            Stage::Board => self.board = self.board - cards,
+            Stage::Hand(i) => self.hands[i] = self.hands[i] - cards,
+        }
+    }
+
+    fn deal(&mut self, stages: &[(Stage, usize)], deck: CardSet, results: &mut [Equity]) {
+        match stages.split_first() {
+            None => self.score(results),
+            Some((&(stage, count), rest)) => self.choose(stage, count, rest, deck, deck, results),
+        }
+    }
+
+    // Picks count more cards for a stage from choices, taking each card before the ones above
+    // it so every combination comes up once, then deals the stages after it from what's left
+    fn choose(&mut self,
+              stage: Stage,
+              count: usize,
+              rest: &[(Stage, usize)],
+              deck: CardSet,
+              mut choices: CardSet,
+              results: &mut [Equity]) {
+        if count == 0 {
+            self.deal(rest, deck, results);
+            return;
+        }
+        while choices.len() >= count {
+            let card: CardSet = CardSet::from(choices.iter().next().unwrap());
+            choices = choices - card;
+            self.add(stage, card);
+            self.choose(stage, count - 1, rest, deck - card, choices, results);
+            self.remove(stage, card);
+        }
+    }
+
+    fn score(&self, results: &mut [Equity]) {
+        let strengths: Vec<u16> = self.hands.iter()
+            .map(|&hand| calc_set_strength(hand | self.board))
+            .collect();
+        showdown(&strengths, results);
+    }
+}
+
+// Checks the known cards are all different and returns the cards left to deal from
+pub(crate) fn remaining_deck(hands: &[CardSet], board: CardSet, dead: &[Card]) -> CardSet {
+    let mut known: CardSet = board | CardSet::from(dead);
+    let mut count: usize = board.len() + dead.len();
+    for &hand in hands {
+        known = known | hand;
+        count += hand.len();
+    }
+    assert_eq!(known.len(), count, "Error: The same card was dealt twice");
+    CardSet::DECK - known
+}
+
+// Records one showdown, the best strength wins and equal best strengths split the pot
+pub(crate) fn showdown(strengths: &[u16], results: &mut [Equity]) {
+    let best: u16 = strengths.iter().copied().max().unwrap_or(0);
+    let winners: usize = strengths.iter().filter(|&&strength| strength == best).count();
+
+    for (result, &strength) in results.iter_mut().zip(strengths) {
+        result.trials += 1;
+        if strength < best {
+            result.losses += 1;
+        } else if winners == 1 {
+            result.wins += 1;
+            result.share += 1.0;
+        } else {
+            result.ties += 1;
+            result.share += 1.0 / winners as f64;
+        }
+    }
+}
+
+#[cfg(test)]
+mod equity_tests {
+    use crate::calc::equity::{exact, exact_holdem, exact_stud, monte_carlo, Equity};
+    use crate::card::{parse_cards, Card, CardSet};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn hole(text: &str) -> [Card; 2] {
+        let cards: Vec<Card> = parse_cards(text).unwrap();
+        [cards[0], cards[1]]
+    }
+
+    #[test]
+    fn test_seeded() {
+        let holes: Vec<[Card; 2]> = vec![hole("AhKh"), hole("QsQd")];
+        let first: Vec<Equity> = monte_carlo(&holes, &[], &[], 2000, &mut StdRng::seed_from_u64(1));
+        let second: Vec<Equity> = monte_carlo(&holes, &[], &[], 2000, &mut StdRng::seed_from_u64(1));
+        assert_eq!(first, second);
+        assert_eq!(first[0].trials, 2000);
+        assert_eq!(first[0].wins, first[1].losses);
+        assert_eq!(first[0].ties, first[1].ties);
+    }
+
+    #[test]
+    fn test_pair_over_pair() {
+        let holes: Vec<[Card; 2]> = vec![hole("AsAh"), hole("KsKh")];
+        let results: Vec<Equity> = monte_carlo(&holes, &[], &[], 20000, &mut StdRng::seed_from_u64(2));
+        assert!((results[0].equity() - 82.0).abs() < 2.0);
+        assert!((results[0].equity() + results[1].equity() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_complete_board() {
+        let holes: Vec<[Card; 2]> = vec![hole("AsKs"), hole("AdKd"), hole("2c3c")];
+        let board: Vec<Card> = parse_cards("AhKhQc 7d 2d").unwrap();
+        let results: Vec<Equity> = monte_carlo(&holes, &board, &[], 10, &mut StdRng::seed_from_u64(3));
+        assert_eq!(results[0].tie_pct(), 100.0);
+        assert_eq!(results[0].equity(), 50.0);
+        assert_eq!(results[2].lose_pct(), 100.0);
+    }
+
+    #[test]
+    fn test_dead_cards() {
+        // With every Queen and Jack gone the drawing hand can only win with a Ten
+        let holes: Vec<[Card; 2]> = vec![hole("QhJh"), hole("AsAc")];
+        let board: Vec<Card> = parse_cards("Kd 2c 3s 9h").unwrap();
+        let dead: Vec<Card> = parse_cards("Qd Qs Qc Jd Js Jc").unwrap();
+        let results: Vec<Equity> = monte_carlo(&holes, &board, &dead, 5000, &mut StdRng::seed_from_u64(4));
+        let tens: f64 = 4.0 / 38.0 * 100.0;
+        assert!((results[0].win_pct() - tens).abs() < 2.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "dealt twice")]
+    fn test_duplicate_cards() {
+        let holes: Vec<[Card; 2]> = vec![hole("AsAh"), hole("AsKh")];
+        monte_carlo(&holes, &[], &[], 1, &mut StdRng::seed_from_u64(5));
+    }
+
+    #[test]
+    fn test_exact_river() {
+        let hands: Vec<Vec<Card>> = vec![parse_cards("AsKs").unwrap(), parse_cards("2c3c").unwrap()];
+        let board: Vec<Card> = parse_cards("AhKhQc 7d 2d").unwrap();
+        let results: Vec<Equity> = exact_holdem(&hands, &board, &[]);
+        assert_eq!((results[0].wins, results[0].trials), (1, 1));
+        assert_eq!(results[1].losses, 1);
+    }
+
+    #[test]
+    fn test_exact_turn() {
+        let hands: Vec<Vec<Card>> = vec![parse_cards("AsAh").unwrap(), parse_cards("KsKh").unwrap()];
+        let board: Vec<Card> = parse_cards("2c 7d 9s Jc").unwrap();
+        let results: Vec<Equity> = exact_holdem(&hands, &board, &[]);
+        assert_eq!(results[0].trials, 44);
+        assert_eq!(results[0].wins, 42);
+        assert_eq!(results[1].wins, 2);
+        assert_eq!(results[0].ties, 0);
+    }
+
+    #[test]
+    fn test_exact_flop_split() {
+        // Both hands hold the same straight unless the board pairs into something better
+        let hands: Vec<Vec<Card>> = vec![parse_cards("Th9h").unwrap(), parse_cards("Td9d").unwrap()];
+        let board: Vec<Card> = parse_cards("Jc Qs Kc").unwrap();
+        let results: Vec<Equity> = exact_holdem(&hands, &board, &[]);
+        assert_eq!(results[0].trials, 990);
+        assert_eq!(results[0].wins, results[1].wins);
+        assert!((results[0].equity() - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_exact_unknown_opponent() {
+        let hands: Vec<Vec<Card>> = vec![parse_cards("AsAh").unwrap(), vec![]];
+        let board: Vec<Card> = parse_cards("2c 7d 9s Jc").unwrap();
+        let exact: Vec<Equity> = exact_holdem(&hands, &board, &[]);
+        assert_eq!(exact[0].trials, 46 * 45 * 44 / 2);
+        assert_eq!(exact[0].wins, exact[1].losses);
+        assert_eq!(exact[0].ties, exact[1].ties);
+        assert!((exact[0].equity() + exact[1].equity() - 100.0).abs() < 1e-9);
+        assert!(exact[0].equity() > 80.0);
+    }
+
+    #[test]
+    fn test_exact_not_enough_cards() {
+        // One card left in the deck can't finish a two card hand
+        let dead: Vec<Card> = Vec::from(CardSet::DECK).split_off(1);
+        let results: Vec<Equity> = exact(&[vec![]], 2, &[], 0, &dead);
+        assert_eq!(results[0].trials, 0);
+    }
+
+    #[test]
+    fn test_exact_stud() {
+        let hands: Vec<Vec<Card>> = vec![parse_cards("9h 9d 9s 9c 2h 3d").unwrap(),
+                                         parse_cards("Ah Kd 7s 4c 2s 8d").unwrap()];
+        let results: Vec<Equity> = exact_stud(&hands, &[]);
+        assert_eq!(results[0].trials, 40 * 39);
+        assert_eq!(results[0].win_pct(), 100.0);
+
+        let finished: Vec<Vec<Card>> = vec![parse_cards("9h 9d 9s 9c 2h 3d 4d").unwrap(),
+                                            parse_cards("Ah Kh Qh Jh Th 3c 4c").unwrap()];
+        let results: Vec<Equity> = exact_stud(&finished, &[]);
+        assert_eq!((results[1].wins, results[1].trials), (1, 1));
+    }
+}
@@ -0,0 +1,305 @@
+use crate::calc::Rank::{self, *};
+use crate::card::{Card, CardSet, Suit::*};
+use std::sync::OnceLock;
+
+// Number of distinct five card hands once suits stop mattering
+pub const CLASSES: u16 = 7462;
+
+// Highest strength in each rank, strengths run from 1 (7-5-4-3-2) to 7462 (royal flush)
+const RANK_CEILINGS: [(u16, Rank); 10] = [(1277, HighCard),
+                                          (4137, OnePair),
+                                          (4995, TwoPair),
+                                          (5853, ThreeOfKind),
+                                          (5863, Straight),
+                                          (7140, Flush),
+                                          (7296, FullHouse),
+                                          (7452, FourOfKind),
+                                          (7461, StraightFlush),
+                                          (7462, RoyalFlush)];
+
+const STRAIGHTS: [u16; 10] = [0x1F00, 0x0F80, 0x07C0, 0x03E0, 0x01F0, 0x00F8, 0x007C, 0x003E, 0x001F, 0x100F];
+
+static TABLES: OnceLock<Tables> = OnceLock::new();
+
+struct Tables {
+    // Strength of the best flush for every 13 bit mask of ranks in one suit
+    flush: Vec<u16>,
+    // Strength of the best non-flush hand for every rank count, one table per hand size
+    ranks: [Vec<u16>; 3],
+    // Perfect hash offsets, indexed by rank, cards left to place and count of the rank
+    offsets: [[[u32; 5]; 8]; 13],
+}
+
+// Calculates the strength of a 5, 6 or 7 card hand, a higher strength is a better hand
+pub fn calc_strength(hand: &[Card]) -> u16 {
+    assert!((5..=7).contains(&hand.len()), "Error: Hand must have 5 to 7 cards");
+    let tables: &Tables = TABLES.get_or_init(build_tables);
+    let mut counts: [u8; 13] = [0; 13];
+    let mut suits: [u16; 4] = [0; 4];
+
+    for card in hand {
+        let rank: usize = card.0.index();
+        counts[rank] += 1;
+        suits[card.1.index()] |= 1 << rank;
+    }
+
+    tables.strength(&counts, &suits, hand.len())
+}
+
+// Calculates the strength of a set of 5, 6 or 7 cards
+pub fn calc_set_strength(cards: CardSet) -> u16 {
+    assert!((5..=7).contains(&cards.len()), "Error: Hand must have 5 to 7 cards");
+    assert_eq!(cards.bits() >> 52, 0, "Error: Jokers must be substituted first");
+    let tables: &Tables = TABLES.get_or_init(build_tables);
+    let suits: [u16; 4] = [cards.suit_mask(Hearts),
+                           cards.suit_mask(Diamonds),
+                           cards.suit_mask(Spades),
+                           cards.suit_mask(Clubs)];
+    let mut counts: [u8; 13] = [0; 13];
+
+    // Bit n of the set is rank n % 13, so each card is counted once
+    let mut bits: u64 = cards.bits();
+    while bits != 0 {
+        counts[bits.trailing_zeros() as usize % 13] += 1;
+        bits &= bits - 1;
+    }
+
+    tables.strength(&counts, &suits, cards.len())
+}
+
+// Finds the rank a strength belongs to
+pub fn strength_rank(strength: u16) -> Rank {
+    RANK_CEILINGS.iter()
+        .find(|&&(ceiling, _)| strength <= ceiling)
+        .map(|&(_, rank)| rank)
+        .expect("Error: Strength out of range")
+}
+
+impl Tables {
+    fn strength(&self, counts: &[u8; 13], suits: &[u16; 4], size: usize) -> u16 {
+        // A flush can't share a hand of seven or less with a full house or quads
+        for &suit in suits.iter() {
+            if suit.count_ones() >= 5 {
+                return self.flush[suit as usize];
+            }
+        }
+        self.ranks[size - 5][hash(&self.offsets, counts, size)]
+    }
+}
+
+// Position of a set of rank counts among all sets holding the same number of cards
+fn hash(offsets: &[[[u32; 5]; 8]; 13], counts: &[u8; 13], size: usize) -> usize {
+    let mut index: u32 = 0;
+    let mut left: usize = size;
+    for (rank, &count) in counts.iter().enumerate() {
+        index += offsets[rank][left][count as usize];
+        left -= count as usize;
+    }
+    index as usize
+}
+
+fn build_tables() -> Tables {
+    // Every distinct five card hand, sorted so a key's position is its strength
+    let mut keys: Vec<u32> = Vec::new();
+    for_each_count(5, &mut |counts| keys.push(best_key(counts)));
+    (0u16..0x2000).filter(|mask| mask.count_ones() == 5)
+        .for_each(|mask| keys.push(flush_key(mask)));
+    keys.sort_unstable();
+    keys.dedup();
+    debug_assert_eq!(keys.len(), CLASSES as usize);
+
+    let strength = |key: u32| keys.binary_search(&key).unwrap() as u16 + 1;
+
+    let flush: Vec<u16> = (0u16..0x2000)
+        .map(|mask| if mask.count_ones() >= 5 { strength(flush_key(mask)) } else { 0 })
+        .collect();
+
+    // ways[n][k] is how many ways k cards fit into n ranks, with at most four per rank
+    let mut ways: [[u32; 8]; 14] = [[0; 8]; 14];
+    ways[0][0] = 1;
+    for n in 1..14 {
+        for k in 0..8 {
+            ways[n][k] = (0..=k.min(4)).map(|count| ways[n - 1][k - count]).sum();
+        }
+    }
+
+    let mut offsets: [[[u32; 5]; 8]; 13] = [[[0; 5]; 8]; 13];
+    for (rank, by_left) in offsets.iter_mut().enumerate() {
+        for (left, by_count) in by_left.iter_mut().enumerate() {
+            for (count, offset) in by_count.iter_mut().enumerate().skip(1) {
+                *offset = (0..count.min(left + 1))
+                    .map(|smaller| ways[12 - rank][left - smaller])
+                    .sum();
+            }
+        }
+    }
+
+    let mut ranks: [Vec<u16>; 3] = [vec![0; ways[13][5] as usize],
+                                    vec![0; ways[13][6] as usize],
+                                    vec![0; ways[13][7] as usize]];
+    for (size, table) in ranks.iter_mut().enumerate() {
+        let cards: usize = size + 5;
+        for_each_count(cards, &mut |counts| {
+            table[hash(&offsets, counts, cards)] = strength(best_key(counts));
+        });
+    }
+
+    Tables { flush, ranks, offsets }
+}
+
+// Calls f with every way of holding n cards, as counts per rank
+fn for_each_count(n: usize, f: &mut dyn FnMut(&[u8; 13])) {
+    fn fill(counts: &mut [u8; 13], rank: usize, left: usize, f: &mut dyn FnMut(&[u8; 13])) {
+        if rank == 13 {
+            if left == 0 {
+                f(counts);
+            }
+            return;
+        }
+        for count in 0..=left.min(4) {
+            counts[rank] = count as u8;
+            fill(counts, rank + 1, left - count, f);
+        }
+        counts[rank] = 0;
+    }
+    fill(&mut [0; 13], 0, n, f);
+}
+
+// Packs a rank category and its tie breaking ranks into a key that sorts like the hand
+fn pack(category: u32, ranks: &[usize]) -> u32 {
+    ranks.iter()
+        .chain(std::iter::repeat(&0))
+        .take(5)
+        .fold(category, |key, &rank| key << 4 | rank as u32)
+}
+
+// Top card of the best straight in a rank mask
+fn straight_top(mask: u16) -> Option<usize> {
+    STRAIGHTS.iter()
+        .position(|&straight| mask & straight == straight)
+        .map(|i| 12 - i)
+}
+
+// Key of the best flush in a mask of ranks of one suit
+fn flush_key(mask: u16) -> u32 {
+    if let Some(top) = straight_top(mask) {
+        return pack(StraightFlush as u32, &[top]);
+    }
+    let ranks: Vec<usize> = (0..13).rev()
+        .filter(|&rank| mask & 1 << rank != 0)
+        .take(5)
+        .collect();
+    pack(Flush as u32, &ranks)
+}
+
+// Key of the best non-flush five card hand in a set of rank counts
+fn best_key(counts: &[u8; 13]) -> u32 {
+    let ranks_with = |n: u8| -> Vec<usize> {
+        (0..13).rev()
+            .filter(|&rank| counts[rank] >= n)
+            .collect()
+    };
+    let others = |used: &[usize]| -> Vec<usize> {
+        (0..13).rev()
+            .filter(|&rank| counts[rank] > 0 && !used.contains(&rank))
+            .collect()
+    };
+    let quads: Vec<usize> = ranks_with(4);
+    let trips: Vec<usize> = ranks_with(3);
+    let pairs: Vec<usize> = ranks_with(2);
+    let mask: u16 = (0..13).filter(|&rank| counts[rank] > 0)
+        .fold(0, |mask, rank| mask | 1 << rank);
+
+    if let Some(&quad) = quads.first() {
+        return pack(FourOfKind as u32, &[quad, others(&[quad])[0]]);
+    }
+    if let Some(&trip) = trips.first() {
+        if let Some(&pair) = pairs.iter().find(|&&pair| pair != trip) {
+            return pack(FullHouse as u32, &[trip, pair]);
+        }
+    }
+    if let Some(top) = straight_top(mask) {
+        return pack(Straight as u32, &[top]);
+    }
+    if let Some(&trip) = trips.first() {
+        let mut ranks: Vec<usize> = vec![trip];
+        ranks.extend(others(&[trip]).into_iter().take(2));
+        return pack(ThreeOfKind as u32, &ranks);
+    }
+    if pairs.len() >= 2 {
+        let mut ranks: Vec<usize> = pairs[..2].to_vec();
+        ranks.push(others(&pairs[..2])[0]);
+        return pack(TwoPair as u32, &ranks);
+    }
+    if let Some(&pair) = pairs.first() {
+        let mut ranks: Vec<usize> = vec![pair];
+        ranks.extend(others(&[pair]).into_iter().take(3));
+        return pack(OnePair as u32, &ranks);
+    }
+    pack(HighCard as u32, &others(&[])[..5])
+}
+
+#[cfg(test)]
+mod lookup_tests {
+    use crate::calc::lookup::{calc_set_strength, calc_strength, strength_rank, CLASSES};
+    use crate::calc::{evaluate, Rank::*};
+    use crate::card::{Card, CardSet, Name::*, Suit::*};
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    #[test]
+    fn test_bounds() {
+        let worst: Vec<Card> = vec![Card(Seven, Hearts),
+                                    Card(Five, Diamonds),
+                                    Card(Four, Spades),
+                                    Card(Three, Clubs),
+                                    Card(Two, Hearts)];
+        let best: Vec<Card> = vec![Card(AceHigh, Spades),
+                                   Card(King, Spades),
+                                   Card(Queen, Spades),
+                                   Card(Jack, Spades),
+                                   Card(Ten, Spades),
+                                   Card(Two, Hearts)];
+        assert_eq!(calc_strength(&worst), 1);
+        assert_eq!(calc_strength(&best), CLASSES);
+    }
+
+    #[test]
+    fn test_strength_rank() {
+        assert_eq!(strength_rank(1), HighCard);
+        assert_eq!(strength_rank(1278), OnePair);
+        assert_eq!(strength_rank(5863), Straight);
+        assert_eq!(strength_rank(5864), Flush);
+        assert_eq!(strength_rank(7461), StraightFlush);
+        assert_eq!(strength_rank(CLASSES), RoyalFlush);
+    }
+
+    #[test]
+    fn test_wheel() {
+        let wheel: Vec<Card> = vec![Card(AceHigh, Hearts),
+                                    Card(Two, Diamonds),
+                                    Card(Three, Spades),
+                                    Card(Four, Diamonds),
+                                    Card(Five, Hearts),
+                                    Card(King, Clubs),
+                                    Card(King, Hearts)];
+        assert_eq!(strength_rank(calc_strength(&wheel)), Straight);
+        assert_eq!(calc_strength(&wheel), 5854);
+    }
+
+    #[test]
+    fn test_matches_evaluate() {
+        let mut rng: StdRng = StdRng::seed_from_u64(7);
+        let mut deck: Vec<Card> = CardSet::DECK.into();
+        for size in 5..=7 {
+            for _ in 0..500 {
+                deck.shuffle(&mut rng);
+                let hand1: &[Card] = &deck[..size];
+                let hand2: &[Card] = &deck[size..(size * 2)];
+                assert_eq!(strength_rank(calc_strength(hand1)), evaluate(hand1).rank);
+                assert_eq!(calc_set_strength(CardSet::from(hand1)), calc_strength(hand1));
+                assert_eq!(calc_strength(hand1).cmp(&calc_strength(hand2)),
+                           evaluate(hand1).cmp(&evaluate(hand2)));
+            }
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use crate::calc::omaha::evaluate_omaha;
+use crate::calc::Rank::{self, *};
+use crate::calc::{check_flush, evaluate, has_straight_ace_high, HandValue};
+use crate::card::{Card, CardSet, Name, Name::*, Suit};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+// Value of a low hand, where a lower value is a better low. In ace-to-five straights and
+// flushes don't count and Aces are always low, so 5-4-3-2-A is the best possible. In
+// deuce-to-seven they count against the hand and Aces are always high, making 7-5-4-3-2 best
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct LowValue {
+    pub rank: Rank,
+    pub names: Vec<Name>,
+}
+
+impl Display for LowValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<String> = self.names.iter()
+            .map(|name| name.to_string())
+            .collect();
+        write!(f, "{}", names.join("-"))
+    }
+}
+
+// A hand's value for both halves of a split pot, low is None when the hand doesn't qualify
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct HiLo {
+    pub high: HandValue,
+    pub low: Option<LowValue>,
+}
+
+// Calculates the best ace-to-five low in a hand of five or more cards
+pub fn ace_to_five(hand: &[Card]) -> LowValue {
+    assert!(hand.len() >= 5, "Error: A low hand needs at least 5 cards");
+    let names: Vec<Name> = hand.iter()
+        .map(|card| if card.0 == AceHigh { AceLow } else { card.0 })
+        .collect();
+    let mut best: Option<LowValue> = None;
+    for_each_five(&names, &mut |five| {
+        let value: LowValue = low_value(five);
+        if best.as_ref().is_none_or(|best| value < *best) {
+            best = Some(value);
+        }
+    });
+    best.unwrap()
+}
+
+// Calculates the best eight-or-better low, five different cards no higher than an Eight
+pub fn eight_or_better(hand: &[Card]) -> Option<LowValue> {
+    Some(ace_to_five(hand)).filter(qualifies)
+}
+
+// Calculates the best eight-or-better Omaha low using exactly two hole cards and three board cards
+pub fn omaha_low(hole: &[Card], board: &[Card]) -> Option<LowValue> {
+    let mut best: Option<LowValue> = None;
+    for (i, &first) in hole.iter().enumerate() {
+        for &second in &hole[(i + 1)..] {
+            for (j, &third) in board.iter().enumerate() {
+                for (k, &fourth) in board.iter().enumerate().skip(j + 1) {
+                    for &fifth in &board[(k + 1)..] {
+                        let low: Option<LowValue> = eight_or_better(&[first, second, third, fourth, fifth]);
+                        if low.is_some() && (best.is_none() || low < best) {
+                            best = low;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+// Calculates the deuce-to-seven low of a five card hand, as in 2-7 Single and Triple Draw
+pub fn deuce_to_seven(hand: &[Card]) -> LowValue {
+    assert_eq!(hand.len(), 5, "Error: Deuce-to-seven ranks 5 card hands");
+    let names: Vec<Name> = hand.iter()
+        .map(|card| if card.0 == AceLow { AceHigh } else { card.0 })
+        .collect();
+    let mut low: LowValue = low_value(&names);
+
+    let straight: bool = has_straight_ace_high(CardSet::from(hand).rank_mask());
+    let flush: bool = check_flush(hand).is_some();
+    low.rank = match (straight, flush) {
+        (true, true) => StraightFlush,
+        (false, true) => Flush,
+        (true, false) => Straight,
+        (false, false) => low.rank,
+    };
+    low
+}
+
+// Finds which player brings in on third street in Razz, the one showing the highest door
+// card with Aces low. Ties go to the highest suit in bridge order, spades down to clubs
+pub fn razz_bring_in(door_cards: &[Card]) -> usize {
+    assert!(!door_cards.is_empty(), "Error: Bring-in needs at least one door card");
+    (0..door_cards.len())
+        .max_by_key(|&i| {
+            let Card(name, suit) = door_cards[i];
+            (if name == AceHigh { AceLow } else { name }, suit_order(suit))
+        })
+        .unwrap()
+}
+
+// Finds which player brings in on third street in high stud games, the one showing the lowest
+// door card with Aces high. Ties go to the lowest suit in bridge order, clubs up to spades
+pub fn stud_bring_in(door_cards: &[Card]) -> usize {
+    assert!(!door_cards.is_empty(), "Error: Bring-in needs at least one door card");
+    (0..door_cards.len())
+        .min_by_key(|&i| {
+            let Card(name, suit) = door_cards[i];
+            (name, suit_order(suit))
+        })
+        .unwrap()
+}
+
+fn suit_order(suit: Suit) -> u8 {
+    match suit {
+        Suit::Clubs => 0,
+        Suit::Diamonds => 1,
+        Suit::Hearts => 2,
+        Suit::Spades => 3,
+    }
+}
+
+// Evaluates a hand for high and an eight-or-better low, as in 7 Card Stud Hi/Lo
+pub fn evaluate_hi_lo(hand: &[Card]) -> HiLo {
+    HiLo { high: evaluate(hand), low: eight_or_better(hand) }
+}
+
+// Evaluates an Omaha Hi/Lo hand, the high and low may use different hole cards
+pub fn evaluate_omaha_hi_lo(hole: &[Card], board: &[Card]) -> HiLo {
+    let (high, _): (HandValue, Vec<Card>) = evaluate_omaha(hole, board);
+    HiLo { high, low: omaha_low(hole, board) }
+}
+
+// Splits a pot between hands listed in seat order, half to the best high and half to the
+// best low, or all to the high when no low qualifies. Ties split their half, which can
+// leave a player with a quarter of the pot, and odd chips go to the high half and then to
+// the earliest seats
+pub fn split_pot(pot: u32, hands: &[HiLo]) -> Vec<u32> {
+    let mut shares: Vec<u32> = vec![0; hands.len()];
+    if hands.is_empty() {
+        return shares;
+    }
+
+    let best_low: Option<&LowValue> = hands.iter()
+        .filter_map(|hand| hand.low.as_ref())
+        .min();
+    let (high_half, low_half): (u32, u32) = match best_low {
+        Some(_) => (pot - pot / 2, pot / 2),
+        None => (pot, 0),
+    };
+
+    let best_high: &HandValue = hands.iter().map(|hand| &hand.high).max().unwrap();
+    let high_winners: Vec<usize> = (0..hands.len())
+        .filter(|&i| &hands[i].high == best_high)
+        .collect();
+    award(high_half, &high_winners, &mut shares);
+
+    if let Some(best_low) = best_low {
+        let low_winners: Vec<usize> = (0..hands.len())
+            .filter(|&i| hands[i].low.as_ref() == Some(best_low))
+            .collect();
+        award(low_half, &low_winners, &mut shares);
+    }
+    shares
+}
+
+// Shares chips evenly between winners, the earliest seats getting any odd chips
+fn award(chips: u32, winners: &[usize], shares: &mut [u32]) {
+    let each: u32 = chips / winners.len() as u32;
+    let odd: usize = (chips % winners.len() as u32) as usize;
+    for (n, &i) in winners.iter().enumerate() {
+        shares[i] += each + if n < odd { 1 } else { 0 };
+    }
+}
+
+fn qualifies(low: &LowValue) -> bool {
+    low.rank == HighCard && low.names[0] <= Eight
+}
+
+// Values five names as a low, pairs and worse count against the hand
+fn low_value(names: &[Name]) -> LowValue {
+    let mut name_count: HashMap<Name, u8> = HashMap::new();
+    names.iter()
+        .for_each(|&name| *name_count.entry(name).or_insert(0) += 1);
+
+    let mut sorted: Vec<Name> = names.to_vec();
+    sorted.sort_by(|a, b| name_count[b].cmp(&name_count[a])
+        .then_with(|| b.cmp(a)));
+
+    let mut counts: Vec<u8> = name_count.values().copied().collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    let rank: Rank = match counts[..] {
+        [4, ..] => FourOfKind,
+        [3, 2, ..] => FullHouse,
+        [3, ..] => ThreeOfKind,
+        [2, 2, ..] => TwoPair,
+        [2, ..] => OnePair,
+        _ => HighCard,
+    };
+    LowValue { rank, names: sorted }
+}
+
+fn for_each_five(names: &[Name], f: &mut dyn FnMut(&[Name])) {
+    fn choose(names: &[Name], chosen: &mut Vec<Name>, f: &mut dyn FnMut(&[Name])) {
+        if chosen.len() == 5 {
+            f(chosen);
+            return;
+        }
+        for i in 0..names.len() {
+            chosen.push(names[i]);
+            choose(&names[(i + 1)..], chosen, f);
+            chosen.pop();
+        }
+    }
+    choose(names, &mut Vec::with_capacity(5), f);
+}
+
+#[cfg(test)]
+mod low_tests {
+    use crate::calc::low::{ace_to_five, deuce_to_seven, eight_or_better, evaluate_hi_lo, evaluate_omaha_hi_lo, omaha_low,
+                           razz_bring_in, split_pot, stud_bring_in, HiLo, LowValue};
+    use crate::calc::Rank::*;
+    use crate::card::{parse_cards, Card, Name::*};
+
+    fn cards(text: &str) -> Vec<Card> {
+        parse_cards(text).unwrap()
+    }
+
+    #[test]
+    fn test_wheel() {
+        let low: LowValue = ace_to_five(&cards("Ah 2h 3h 4h 5h"));
+        assert_eq!(low, LowValue { rank: HighCard, names: vec![Five, Four, Three, Two, AceLow] });
+        assert_eq!(low.to_string(), "5-4-3-2-A");
+        assert!(low < ace_to_five(&cards("6c 4d 3s 2h Ac")));
+    }
+
+    #[test]
+    fn test_ace_to_five() {
+        assert!(ace_to_five(&cards("8h 6d 4s 3c 2h")) < ace_to_five(&cards("8c 7d 3s 2c Ah")));
+        assert!(ace_to_five(&cards("Kh Qd Js 9c 8h")) < ace_to_five(&cards("2h 2d 3s 4c 5h")));
+        assert!(ace_to_five(&cards("2h 2d 3s 4c 5h")) < ace_to_five(&cards("2h 2d 3s 3c 5h")));
+        assert_eq!(ace_to_five(&cards("Kh 7d 2s 9c 3h 4d 2c")).names, vec![Nine, Seven, Four, Three, Two]);
+        assert_eq!(ace_to_five(&cards("Kh Kd Ks 2c 2h 3d 3c")).rank, TwoPair);
+    }
+
+    #[test]
+    fn test_eight_or_better() {
+        assert!(eight_or_better(&cards("8h 7d 5s 3c 2h")).is_some());
+        assert!(eight_or_better(&cards("9h 7d 5s 3c 2h")).is_none());
+        assert!(eight_or_better(&cards("7h 7d 5s 3c 2h")).is_none());
+        assert_eq!(eight_or_better(&cards("9h 7d 5s 3c 2h Ac Kd")).unwrap().names,
+                   vec![Seven, Five, Three, Two, AceLow]);
+    }
+
+    #[test]
+    fn test_omaha_low() {
+        // Only one low card in the hand, so there's no low even though the board has four
+        assert!(omaha_low(&cards("Ah Kd Qs Qc"), &cards("2h 3d 5s 7c Tc")).is_none());
+        // The Two on the board would pair the hand, so the Seven has to play
+        let low: LowValue = omaha_low(&cards("Ah 2d Qs Qc"), &cards("2h 3d 5s 7c Tc")).unwrap();
+        assert_eq!(low.names, vec![Seven, Five, Three, Two, AceLow]);
+        assert_eq!(evaluate_omaha_hi_lo(&cards("Ah 2d Qs Qc"), &cards("2h 3d 5s 7c Tc")).low, Some(low));
+    }
+
+    #[test]
+    fn test_split_high_only() {
+        let hands: Vec<HiLo> = vec![evaluate_hi_lo(&cards("Ah Ad Ks Kc 9h 9d Qs")),
+                                    evaluate_hi_lo(&cards("2h 3d Ts Jc Qh 9c 9s"))];
+        assert_eq!(split_pot(101, &hands), vec![101, 0]);
+    }
+
+    #[test]
+    fn test_split_high_and_low() {
+        let hands: Vec<HiLo> = vec![evaluate_hi_lo(&cards("Ah Ad Ks Kc 9h 9d Qs")),
+                                    evaluate_hi_lo(&cards("2h 3d 4s 6c 7h 9c Ts"))];
+        assert_eq!(split_pot(101, &hands), vec![51, 50]);
+    }
+
+    #[test]
+    fn test_quartering() {
+        let board: Vec<Card> = cards("2h 3d 7s Kc Kd");
+        let hands: Vec<HiLo> = vec![evaluate_omaha_hi_lo(&cards("Ah 4c Ks Qh"), &board),
+                                    evaluate_omaha_hi_lo(&cards("Ad 4d Jh Jc"), &board),
+                                    evaluate_omaha_hi_lo(&cards("Qs Qc Ts 9s"), &board)];
+        assert_eq!(hands[0].low, hands[1].low);
+        assert_eq!(split_pot(120, &hands), vec![60 + 30, 30, 0]);
+    }
+
+    #[test]
+    fn test_scoop() {
+        let hands: Vec<HiLo> = vec![evaluate_hi_lo(&cards("Ah 2d 3s 4c 5h 9c Ts")),
+                                    evaluate_hi_lo(&cards("Kh Kd 8s 7c 6h Jc Ts"))];
+        assert_eq!(hands[0].high.rank, Straight);
+        assert_eq!(split_pot(100, &hands), vec![100, 0]);
+    }
+
+    #[test]
+    fn test_deuce_to_seven_best() {
+        let best: LowValue = deuce_to_seven(&cards("7h 5d 4s 3c 2h"));
+        assert_eq!(best, LowValue { rank: HighCard, names: vec![Seven, Five, Four, Three, Two] });
+        assert!(best < deuce_to_seven(&cards("7h 6d 4s 3c 2h")));
+        assert!(best < deuce_to_seven(&cards("8h 5d 4s 3c 2h")));
+    }
+
+    #[test]
+    fn test_deuce_to_seven_aces_high() {
+        // A-2-3-4-5 isn't a straight, just an Ace high
+        let wheel: LowValue = deuce_to_seven(&cards("Ah 2d 3s 4c 5h"));
+        assert_eq!(wheel.rank, HighCard);
+        assert_eq!(wheel.names[0], AceHigh);
+        assert!(deuce_to_seven(&cards("Kh Qd Js 9c 8h")) < wheel);
+        assert!(wheel < deuce_to_seven(&cards("2h 2d 3s 4c 5h")));
+    }
+
+    #[test]
+    fn test_deuce_to_seven_straights_and_flushes() {
+        let straight: LowValue = deuce_to_seven(&cards("6h 5d 4s 3c 2h"));
+        assert_eq!(straight.rank, Straight);
+        assert!(deuce_to_seven(&cards("Ah Ad Ks Kc Qh")) < straight);
+        assert_eq!(deuce_to_seven(&cards("9h 7h 5h 3h 2h")).rank, Flush);
+        assert!(straight < deuce_to_seven(&cards("9h 7h 5h 3h 2h")));
+        assert_eq!(deuce_to_seven(&cards("Ah Kh Qh Jh Th")).rank, StraightFlush);
+        assert!(deuce_to_seven(&cards("7h 6h 5h 4h 3h")) < deuce_to_seven(&cards("Ah Kh Qh Jh Th")));
+    }
+
+    #[test]
+    fn test_razz_bring_in() {
+        assert_eq!(razz_bring_in(&cards("3h Kd 7s")), 1);
+        // Aces are low, so the Two brings in
+        assert_eq!(razz_bring_in(&cards("Ah 2d")), 1);
+        assert_eq!(razz_bring_in(&cards("Qs Qc Qh Qd")), 0);
+        assert_eq!(razz_bring_in(&cards("9c 9d")), 1);
+    }
+
+    #[test]
+    fn test_stud_bring_in() {
+        assert_eq!(stud_bring_in(&cards("3h Kd 7s")), 0);
+        // Aces are high, so the Two brings in
+        assert_eq!(stud_bring_in(&cards("2h Ad")), 0);
+        assert_eq!(stud_bring_in(&cards("Qs Qc Qh Qd")), 1);
+    }
+
+    #[test]
+    fn test_razz_ignores_straights_and_flushes() {
+        let low: LowValue = ace_to_five(&cards("5h 4h 3h 2h Ah Kc Kd"));
+        assert_eq!(low.rank, HighCard);
+        assert_eq!(low.to_string(), "5-4-3-2-A");
+    }
+}
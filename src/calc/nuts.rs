@@ -0,0 +1,107 @@
+use crate::calc::enumerate::combinations;
+use crate::calc::{evaluate, HandValue};
+use crate::card::{Card, CardSet};
+
+// Holdings that tie with each other on a board, along with the hand they make
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tier {
+    pub value: HandValue,
+    pub holdings: Vec<[Card; 2]>,
+}
+
+// Ranks every two card holding on a flop, turn or river from strongest to weakest, with
+// holdings that make the same hand grouped into one tier. The first tier is the nuts
+pub fn rank_holdings(board: &[Card]) -> Vec<Tier> {
+    assert!((3..=5).contains(&board.len()), "Error: Ranking holdings needs a flop, turn or river");
+    let board_set: CardSet = CardSet::from(board);
+    let mut hand: Vec<Card> = board.to_vec();
+    let mut valued: Vec<(HandValue, [Card; 2])> = combinations(&Vec::from(CardSet::DECK - board_set), 2)
+        .map(|holding| {
+            let mut cards: Vec<Card> = holding.into();
+            cards.sort_by(|a, b| b.cmp(a));
+            hand.extend(&cards);
+            let value: HandValue = evaluate(&hand);
+            hand.truncate(board.len());
+            (value, [cards[0], cards[1]])
+        })
+        .collect();
+    valued.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)));
+
+    let mut tiers: Vec<Tier> = Vec::new();
+    for (value, holding) in valued {
+        match tiers.last_mut() {
+            Some(tier) if tier.value == value => tier.holdings.push(holding),
+            _ => tiers.push(Tier { value, holdings: vec![holding] }),
+        }
+    }
+    tiers
+}
+
+// Finds the best hand possible on a board and every holding that makes it
+pub fn calc_nuts(board: &[Card]) -> Tier {
+    rank_holdings(board).swap_remove(0)
+}
+
+// Position of a holding's tier on the board, 1 being the nuts
+pub fn nut_rank(board: &[Card], holding: &[Card; 2]) -> usize {
+    let held: CardSet = CardSet::from(&holding[..]);
+    rank_holdings(board).iter()
+        .position(|tier| tier.holdings.iter().any(|other| CardSet::from(&other[..]) == held))
+        .expect("Error: The holding shares a card with the board")
+        + 1
+}
+
+#[cfg(test)]
+mod nuts_tests {
+    use crate::calc::nuts::{calc_nuts, nut_rank, rank_holdings, Tier};
+    use crate::calc::Rank::*;
+    use crate::card::{parse_cards, Card, Name::*, Suit::*};
+
+    fn cards(text: &str) -> Vec<Card> {
+        parse_cards(text).unwrap()
+    }
+
+    #[test]
+    fn test_nuts() {
+        let nuts: Tier = calc_nuts(&cards("Ah 7h 2h"));
+        assert_eq!(nuts.value.rank, Flush);
+        assert_eq!(nuts.holdings, vec![[Card(King, Hearts), Card(Queen, Hearts)]]);
+
+        let nuts: Tier = calc_nuts(&cards("Kd 7c 2h"));
+        assert_eq!(nuts.value.rank, ThreeOfKind);
+        assert_eq!(nuts.holdings.len(), 3);
+
+        let nuts: Tier = calc_nuts(&cards("Ts Js Qs Ks As"));
+        assert_eq!(nuts.value.rank, RoyalFlush);
+        assert_eq!(nuts.holdings.len(), 47 * 46 / 2);
+    }
+
+    #[test]
+    fn test_tiers() {
+        let tiers: Vec<Tier> = rank_holdings(&cards("Kd 7c 2h"));
+        assert_eq!(tiers.iter().map(|tier| tier.holdings.len()).sum::<usize>(), 49 * 48 / 2);
+        assert!(tiers.windows(2).all(|pair| pair[0].value > pair[1].value));
+        assert_eq!(tiers[0].value.rank, ThreeOfKind);
+        assert_eq!(tiers[0].holdings.len(), 3);
+        // Sets of Sevens then Twos follow the Kings
+        assert_eq!(tiers[1].holdings.len(), 3);
+        assert_eq!(tiers[1].value.kickers[0], Seven);
+    }
+
+    #[test]
+    fn test_board_plays() {
+        // Nobody can beat a royal flush on the board, so every holding ties
+        let tiers: Vec<Tier> = rank_holdings(&cards("Ts Js Qs Ks As"));
+        assert_eq!(tiers.len(), 1);
+        assert_eq!(tiers[0].value.rank, RoyalFlush);
+        assert_eq!(tiers[0].holdings.len(), 47 * 46 / 2);
+    }
+
+    #[test]
+    fn test_nut_rank() {
+        let board: Vec<Card> = cards("Kd 7c 2h");
+        assert_eq!(nut_rank(&board, &[Card(King, Hearts), Card(King, Spades)]), 1);
+        assert_eq!(nut_rank(&board, &[Card(Seven, Diamonds), Card(Seven, Spades)]), 2);
+        assert_eq!(nut_rank(&board, &[Card(Two, Diamonds), Card(Seven, Spades)]), 6);
+    }
+}
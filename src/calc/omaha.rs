@@ -0,0 +1,79 @@
+use crate::calc::lookup::calc_strength;
+use crate::calc::{calc_best_hand, evaluate, HandValue};
+use crate::card::Card;
+
+// Evaluates an Omaha hand, which must use exactly two hole cards and three board cards,
+// returning its value and the five cards chosen
+pub fn evaluate_omaha(hole: &[Card], board: &[Card]) -> (HandValue, Vec<Card>) {
+    assert!((4..=6).contains(&hole.len()), "Error: Omaha needs 4 to 6 hole cards");
+    assert!((3..=5).contains(&board.len()), "Error: Omaha needs 3 to 5 board cards");
+
+    let mut best: Option<(u16, [Card; 5])> = None;
+    for (i, &first) in hole.iter().enumerate() {
+        for &second in &hole[(i + 1)..] {
+            for (j, &third) in board.iter().enumerate() {
+                for (k, &fourth) in board.iter().enumerate().skip(j + 1) {
+                    for &fifth in &board[(k + 1)..] {
+                        let five: [Card; 5] = [first, second, third, fourth, fifth];
+                        let strength: u16 = calc_strength(&five);
+                        if best.is_none_or(|(most, _)| strength > most) {
+                            best = Some((strength, five));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let (_, five): (u16, [Card; 5]) = best.unwrap();
+    let value: HandValue = evaluate(&five);
+    let chosen: Vec<Card> = calc_best_hand(&five, value.rank);
+    (value, chosen)
+}
+
+#[cfg(test)]
+mod omaha_tests {
+    use crate::calc::omaha::evaluate_omaha;
+    use crate::calc::{evaluate, Rank::*};
+    use crate::card::{parse_cards, Card, CardSet};
+
+    fn cards(text: &str) -> Vec<Card> {
+        parse_cards(text).unwrap()
+    }
+
+    #[test]
+    fn test_needs_two_hole_cards() {
+        // One heart in the hand is no flush, even with four on the board
+        let (value, chosen) = evaluate_omaha(&cards("Ah Kc 7d 7s"), &cards("2h 5h 9h Jh 3c"));
+        assert_eq!(value.rank, OnePair);
+        assert_eq!(chosen.len(), 5);
+        assert_eq!(evaluate(&[cards("Ah Kc 7d 7s"), cards("2h 5h 9h Jh 3c")].concat()).rank, Flush);
+    }
+
+    #[test]
+    fn test_uses_three_board_cards() {
+        // Four of a kind in the hand only plays as a pair
+        let (value, _) = evaluate_omaha(&cards("9h 9d 9s 9c"), &cards("2h 5d Kc"));
+        assert_eq!(value.rank, OnePair);
+    }
+
+    #[test]
+    fn test_chosen_cards() {
+        let hole: Vec<Card> = cards("Ah Kh Qs Qd");
+        let board: Vec<Card> = cards("Th Jh Qh 3c 2d");
+        let (value, chosen) = evaluate_omaha(&hole, &board);
+        assert_eq!(value.rank, RoyalFlush);
+        let used: CardSet = CardSet::from(&chosen);
+        assert_eq!((used & CardSet::from(&hole)).len(), 2);
+        assert_eq!((used & CardSet::from(&board)).len(), 3);
+    }
+
+    #[test]
+    fn test_five_and_six_cards() {
+        let (value, _) = evaluate_omaha(&cards("Ah Ad Kc Ks 7s"), &cards("As Kd 2c"));
+        assert_eq!(value.rank, ThreeOfKind);
+        let (value, chosen) = evaluate_omaha(&cards("Ah Ad Kc Ks 7s 7c"), &cards("As Kd 7d 2c 3c"));
+        assert_eq!(value.rank, ThreeOfKind);
+        assert_eq!(chosen[0].0, cards("As")[0].0);
+    }
+}
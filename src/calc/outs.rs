@@ -0,0 +1,242 @@
+use crate::calc::{calc_rank, evaluate, has_straight, Rank};
+use crate::card::{Card, CardSet, SUITS};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Draw {
+    FlushDraw,
+    OpenEnded,
+    DoubleGutshot,
+    Gutshot,
+    BackdoorFlush,
+    BackdoorStraight,
+}
+
+impl Display for Draw {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let draw_str = match self {
+            Draw::FlushDraw => "Flush Draw",
+            Draw::OpenEnded => "Open-Ended Straight Draw",
+            Draw::DoubleGutshot => "Double Gutshot Straight Draw",
+            Draw::Gutshot => "Gutshot Straight Draw",
+            Draw::BackdoorFlush => "Backdoor Flush Draw",
+            Draw::BackdoorStraight => "Backdoor Straight Draw",
+        };
+        write!(f, "{}", draw_str)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outs {
+    // Cards that lift the hand into a better rank than the board alone makes, with the rank
+    // they make
+    pub improving: Vec<(Card, Rank)>,
+    // Cards that change who is ahead of the opponent, empty without an opponent
+    pub flips: Vec<Card>,
+    pub draws: Vec<Draw>,
+    // Chance of hitting an improving card on the very next card
+    pub next_card: f64,
+    // Chance of hitting an improving card by the river
+    pub by_river: f64,
+}
+
+// Finds the cards that help a Hold'em hand on the flop or turn
+pub fn calc_outs(hole: &[Card], board: &[Card], opponent: Option<&[Card]>) -> Outs {
+    assert!(board.len() == 3 || board.len() == 4, "Error: Outs need a flop or turn");
+    let mut hand: Vec<Card> = hole.to_vec();
+    hand.extend(board);
+    let mut known: CardSet = CardSet::from(&hand);
+    if let Some(opponent) = opponent {
+        known = known | CardSet::from(opponent);
+    }
+    let unseen: CardSet = CardSet::DECK - known;
+
+    // A card that only improves the board, like one pairing it, helps everyone and isn't an out
+    let rank: Rank = calc_rank(&hand);
+    let mut board: Vec<Card> = board.to_vec();
+    let improving: Vec<(Card, Rank)> = unseen.iter()
+        .filter_map(|card| {
+            hand.push(card);
+            board.push(card);
+            let improved: Rank = calc_rank(&hand);
+            let shared: Rank = calc_rank(&board);
+            hand.pop();
+            board.pop();
+            (improved > rank && improved > shared).then_some((card, improved))
+        })
+        .collect();
+    let board: &[Card] = &board;
+
+    let flips: Vec<Card> = match opponent {
+        None => Vec::new(),
+        Some(opponent) => {
+            let mut theirs: Vec<Card> = opponent.to_vec();
+            theirs.extend(board);
+            let standing: Ordering = evaluate(&hand).cmp(&evaluate(&theirs));
+            unseen.iter()
+                .filter(|&card| {
+                    hand.push(card);
+                    theirs.push(card);
+                    let after: Ordering = evaluate(&hand).cmp(&evaluate(&theirs));
+                    hand.pop();
+                    theirs.pop();
+                    after != standing
+                })
+                .collect()
+        }
+    };
+
+    let outs: f64 = improving.len() as f64;
+    let left: f64 = unseen.len() as f64;
+    let next_card: f64 = outs / left;
+    let by_river: f64 = match board.len() {
+        3 => 1.0 - (left - outs) / left * (left - 1.0 - outs) / (left - 1.0),
+        _ => next_card,
+    };
+
+    Outs { improving, flips, draws: calc_draws(hole, board), next_card, by_river }
+}
+
+// Classifies the flush and straight draws a hand holds
+pub fn calc_draws(hole: &[Card], board: &[Card]) -> Vec<Draw> {
+    let holding: CardSet = CardSet::from(hole);
+    let cards: CardSet = holding | CardSet::from(board);
+    let mut draws: Vec<Draw> = Vec::new();
+
+    // Flush draws only count when a hole card is part of them
+    let flush_made: bool = SUITS.iter().any(|&suit| cards.suit_mask(suit).count_ones() >= 5);
+    if !flush_made {
+        for &suit in SUITS.iter() {
+            if holding.suit_mask(suit) == 0 {
+                continue;
+            }
+            match cards.suit_mask(suit).count_ones() {
+                4 => draws.push(Draw::FlushDraw),
+                3 if board.len() == 3 => draws.push(Draw::BackdoorFlush),
+                _ => {}
+            }
+        }
+    }
+
+    // Straight draws only count when a hole card is part of the straight they make
+    let mask: u16 = cards.rank_mask();
+    let board_mask: u16 = CardSet::from(board).rank_mask();
+    if !has_straight(mask) {
+        let missing: Vec<u16> = (0..13)
+            .map(|rank| 1 << rank)
+            .filter(|&bit| mask & bit == 0)
+            .collect();
+        let completing: u16 = missing.iter()
+            .filter(|&&bit| completes(mask, board_mask, bit))
+            .fold(0, |completing, &bit| completing | bit);
+        match completing.count_ones() {
+            0 => {
+                let backdoor: bool = board.len() == 3 && missing.iter()
+                    .any(|&first| missing.iter().any(|&second| completes(mask, board_mask, first | second)));
+                if backdoor {
+                    draws.push(Draw::BackdoorStraight);
+                }
+            }
+            1 => draws.push(Draw::Gutshot),
+            _ if is_open_ended(mask, completing) => draws.push(Draw::OpenEnded),
+            _ => draws.push(Draw::DoubleGutshot),
+        }
+    }
+    draws
+}
+
+// Every run of five ranks as a rank mask, starting with the wheel
+fn straights() -> impl Iterator<Item=u16> {
+    std::iter::once(0x100F).chain((0..9).map(|low| 0x1F << low))
+}
+
+// Checks the extra ranks make a straight the board and those ranks alone don't
+fn completes(mask: u16, board_mask: u16, extra: u16) -> bool {
+    straights().any(|straight| straight & !(mask | extra) == 0 && straight & !(board_mask | extra) != 0)
+}
+
+// Checks the completing ranks sit at both ends of four ranks in a row, Ace to Five counting as
+// open at the bottom and a run up to the Ace being open at one end only
+fn is_open_ended(mask: u16, completing: u16) -> bool {
+    (0..9).any(|low| {
+        let run: u16 = 0xF << low;
+        let below: u16 = if low == 0 { 1 << 12 } else { 1 << (low - 1) };
+        let above: u16 = 1 << (low + 4);
+        mask & run == run && completing & below != 0 && completing & above != 0
+    })
+}
+
+#[cfg(test)]
+mod outs_tests {
+    use crate::calc::outs::{calc_draws, calc_outs, Draw, Outs};
+    use crate::calc::Rank::*;
+    use crate::card::{parse_cards, Card, Name::*, Suit::*};
+
+    fn cards(text: &str) -> Vec<Card> {
+        parse_cards(text).unwrap()
+    }
+
+    #[test]
+    fn test_flush_draw_outs() {
+        let outs: Outs = calc_outs(&cards("AhKh"), &cards("7h 2h 9c"), None);
+        assert_eq!(outs.draws, vec![Draw::FlushDraw]);
+        let flushes: usize = outs.improving.iter()
+            .filter(|&&(_, rank)| rank == Flush)
+            .count();
+        assert_eq!(flushes, 9);
+        // Nine hearts plus three Aces and three Kings, cards pairing the board help everyone
+        assert_eq!(outs.improving.len(), 9 + 6);
+        let pairing: usize = outs.improving.iter()
+            .filter(|&&(card, _)| card.1 != Hearts && [Seven, Two, Nine].contains(&card.0))
+            .count();
+        assert_eq!(pairing, 0);
+        assert!((outs.next_card - 15.0 / 47.0).abs() < 1e-9);
+        assert!(outs.by_river > outs.next_card);
+    }
+
+    #[test]
+    fn test_turn_probability() {
+        let outs: Outs = calc_outs(&cards("8d9c"), &cards("Th Js 2c 2h"), None);
+        assert!(outs.draws.contains(&Draw::OpenEnded));
+        assert_eq!(outs.next_card, outs.by_river);
+    }
+
+    #[test]
+    fn test_straight_draws() {
+        assert_eq!(calc_draws(&cards("8d9c"), &cards("Th Js 2c")), vec![Draw::OpenEnded]);
+        assert_eq!(calc_draws(&cards("8d9c"), &cards("Jh Qs 2c")), vec![Draw::Gutshot]);
+        assert_eq!(calc_draws(&cards("5d9c"), &cards("7h 8s Jd")), vec![Draw::DoubleGutshot]);
+        assert_eq!(calc_draws(&cards("2d3c"), &cards("4h 5s Kd")), vec![Draw::OpenEnded]);
+        assert_eq!(calc_draws(&cards("Jd9c"), &cards("Qh Ks 2d")), vec![Draw::Gutshot]);
+        assert_eq!(calc_draws(&cards("Ad2c"), &cards("3h 4s Kd")), vec![Draw::Gutshot]);
+        assert_eq!(calc_draws(&cards("8d9c"), &cards("Th 2s 3d")), vec![Draw::BackdoorStraight]);
+        assert_eq!(calc_draws(&cards("8d9d"), &cards("Td Jd Qs")), vec![Draw::FlushDraw]);
+    }
+
+    #[test]
+    fn test_board_straight_draw() {
+        // Four to a straight on the board is everyone's draw, so a Four or Nine is no out
+        let outs: Outs = calc_outs(&cards("AcKd"), &cards("5h 6s 7d 8c"), None);
+        assert!(outs.draws.is_empty());
+        assert!(outs.improving.iter().all(|&(card, _)| card.0 != Four && card.0 != Nine));
+        assert_eq!(calc_draws(&cards("Ac2d"), &cards("6h 7s 8d 9c")), vec![]);
+    }
+
+    #[test]
+    fn test_backdoor_flush() {
+        assert_eq!(calc_draws(&cards("AhKh"), &cards("7h 2c 2d")), vec![Draw::BackdoorFlush]);
+        assert!(calc_draws(&cards("AhKh"), &cards("7h 2c 2d 3s")).is_empty());
+        // The board alone holding four hearts is not the player's draw
+        assert!(calc_draws(&cards("AcKc"), &cards("7h 2h 3h 9h")).iter().all(|&draw| draw != Draw::FlushDraw));
+    }
+
+    #[test]
+    fn test_flips() {
+        let outs: Outs = calc_outs(&cards("QhQd"), &cards("Ks 7c 2d 3h"), Some(&cards("KdTc")));
+        let mut flips: Vec<Card> = outs.flips.clone();
+        flips.sort();
+        assert_eq!(flips, vec![Card(Queen, Spades), Card(Queen, Clubs)]);
+        assert!(outs.improving.contains(&(Card(Queen, Spades), ThreeOfKind)));
+    }
+}
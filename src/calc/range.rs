@@ -0,0 +1,353 @@
+use crate::calc::enumerate::combinations;
+use crate::calc::equity::{remaining_deck, sample, Equity};
+use crate::card::{parse_cards, Card, CardSet, Name, NAMES, SUITS};
+use rand::Rng;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// Times a deal is retried before the ranges are taken to block each other completely
+const MAX_REDEALS: u32 = 10_000;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseRangeError {
+    InvalidHand(String),
+    InvalidWeight(String),
+}
+
+impl Display for ParseRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseRangeError::InvalidHand(token) => write!(f, "Error: Invalid range hand '{}'", token),
+            ParseRangeError::InvalidWeight(token) => write!(f, "Error: Invalid range weight '{}'", token),
+        }
+    }
+}
+
+impl Error for ParseRangeError {}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum Suitedness {
+    Suited,
+    Offsuit,
+    Any,
+}
+
+// Two card names and whether they share a suit, such as AKs or 77, names are indexes into NAMES
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct HandClass {
+    pub(crate) high: usize,
+    pub(crate) low: usize,
+    pub(crate) suitedness: Suitedness,
+}
+
+impl HandClass {
+    pub(crate) fn combos(&self) -> Vec<[Card; 2]> {
+        let mut combos: Vec<[Card; 2]> = Vec::new();
+        for (i, &suit1) in SUITS.iter().enumerate() {
+            for (j, &suit2) in SUITS.iter().enumerate() {
+                let keep: bool = match self.suitedness {
+                    _ if self.high == self.low => i < j,
+                    Suitedness::Suited => i == j,
+                    Suitedness::Offsuit => i != j,
+                    Suitedness::Any => true,
+                };
+                if keep {
+                    combos.push([Card(NAMES[self.high], suit1), Card(NAMES[self.low], suit2)]);
+                }
+            }
+        }
+        combos
+    }
+}
+
+impl FromStr for HandClass {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<HandClass, ParseRangeError> {
+        let invalid = || ParseRangeError::InvalidHand(s.to_string());
+        let chars: Vec<char> = s.chars().collect();
+        if chars.len() < 2 || chars.len() > 3 {
+            return Err(invalid());
+        }
+        // Jokers aren't part of any range
+        let name = |c: char| -> Result<Name, ParseRangeError> {
+            match c.to_string().parse::<Name>() {
+                Ok(Name::Joker) | Err(_) => Err(invalid()),
+                Ok(name) => Ok(name),
+            }
+        };
+        let first: Name = name(chars[0])?;
+        let second: Name = name(chars[1])?;
+        let suitedness: Suitedness = match chars.get(2) {
+            None => Suitedness::Any,
+            Some('s') | Some('S') => Suitedness::Suited,
+            Some('o') | Some('O') => Suitedness::Offsuit,
+            Some(_) => return Err(invalid()),
+        };
+        if first == second && suitedness != Suitedness::Any {
+            return Err(invalid());
+        }
+        Ok(HandClass {
+            high: first.index().max(second.index()),
+            low: first.index().min(second.index()),
+            suitedness,
+        })
+    }
+}
+
+// A weighted set of two card holdings, written like "QQ+, AKs, A2s-A5s, KTo+, AA:0.5"
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Range {
+    combos: Vec<([Card; 2], f64)>,
+}
+
+impl Range {
+    pub fn new() -> Range {
+        Range::default()
+    }
+
+    // Every one of the 1326 holdings, an opponent who could have anything
+    pub fn any_two() -> Range {
+        let combos: Vec<([Card; 2], f64)> = combinations(&Vec::from(CardSet::DECK), 2)
+            .map(|combo| {
+                let cards: Vec<Card> = combo.into();
+                ([cards[0], cards[1]], 1.0)
+            })
+            .collect();
+        Range { combos }
+    }
+
+    // Adds a holding to the range, replacing its weight if it's already there
+    pub fn add(&mut self, combo: [Card; 2], weight: f64) {
+        let key: CardSet = CardSet::from(&combo[..]);
+        match self.combos.iter_mut().find(|(held, _)| CardSet::from(&held[..]) == key) {
+            Some(entry) => entry.1 = weight,
+            None => self.combos.push((combo, weight)),
+        }
+    }
+
+    pub fn combos(&self) -> &[([Card; 2], f64)] {
+        &self.combos
+    }
+
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    // Holdings still possible once the known cards are removed from the deck
+    pub fn available(&self, known: CardSet) -> Vec<([Card; 2], f64)> {
+        self.combos.iter()
+            .filter(|&&(combo, weight)| weight > 0.0 && !known.contains(combo[0]) && !known.contains(combo[1]))
+            .copied()
+            .collect()
+    }
+}
+
+impl FromStr for Range {
+    type Err = ParseRangeError;
+
+    fn from_str(s: &str) -> Result<Range, ParseRangeError> {
+        let mut range: Range = Range::new();
+        for token in s.split(|c: char| c == ',' || c.is_whitespace()).filter(|token| !token.is_empty()) {
+            let (hands, weight): (&str, f64) = match token.split_once(':') {
+                Some((hands, weight)) => match weight.parse::<f64>() {
+                    Ok(weight) if (0.0..=1.0).contains(&weight) => (hands, weight),
+                    _ => return Err(ParseRangeError::InvalidWeight(token.to_string())),
+                },
+                None => (token, 1.0),
+            };
+            for combo in expand(hands)? {
+                range.add(combo, weight);
+            }
+        }
+        Ok(range)
+    }
+}
+
+// Expands one range token into the holdings it covers
+fn expand(token: &str) -> Result<Vec<[Card; 2]>, ParseRangeError> {
+    let invalid = || ParseRangeError::InvalidHand(token.to_string());
+
+    // A specific holding such as AhKd
+    if let Ok(cards) = parse_cards(token) {
+        return match cards[..] {
+            [first, second] if first.0 != Name::Joker && second.0 != Name::Joker => Ok(vec![[first, second]]),
+            _ => Err(invalid()),
+        };
+    }
+
+    let classes: Vec<HandClass> = if let Some(start) = token.strip_suffix('+') {
+        let start: HandClass = start.parse().map_err(|_| invalid())?;
+        if start.high == start.low {
+            (start.high..NAMES.len()).map(|name| HandClass { high: name, low: name, ..start }).collect()
+        } else {
+            (start.low..start.high).map(|low| HandClass { low, ..start }).collect()
+        }
+    } else if let Some((from, to)) = token.split_once('-') {
+        let from: HandClass = from.parse().map_err(|_| invalid())?;
+        let to: HandClass = to.parse().map_err(|_| invalid())?;
+        let pairs: bool = from.high == from.low && to.high == to.low;
+        if pairs {
+            (from.high.min(to.high)..=from.high.max(to.high))
+                .map(|name| HandClass { high: name, low: name, ..from })
+                .collect()
+        } else if from.high == to.high && from.suitedness == to.suitedness && from.high != from.low && to.high != to.low {
+            (from.low.min(to.low)..=from.low.max(to.low))
+                .map(|low| HandClass { low, ..from })
+                .collect()
+        } else {
+            return Err(invalid());
+        }
+    } else {
+        vec![token.parse().map_err(|_| invalid())?]
+    };
+
+    Ok(classes.iter().flat_map(HandClass::combos).collect())
+}
+
+// Estimates Texas Hold'em equity between ranges by dealing random holdings and run-outs,
+// a single hand such as "AhKd" is just a range of one holding
+pub fn range_equity<R: Rng>(ranges: &[Range],
+                            board: &[Card],
+                            dead: &[Card],
+                            trials: u64,
+                            rng: &mut R) -> Vec<Equity> {
+    let deck: CardSet = remaining_deck(&[], CardSet::from(board), dead);
+    let choices: Vec<Vec<([Card; 2], f64)>> = ranges.iter()
+        .map(|range| range.available(CardSet::DECK - deck))
+        .collect();
+    assert!(choices.iter().all(|choice| !choice.is_empty()), "Error: Range has no holdings left");
+    let totals: Vec<f64> = choices.iter()
+        .map(|choice| choice.iter().map(|&(_, weight)| weight).sum())
+        .collect();
+    sample(ranges.len(), board, deck, trials, rng, |hands, rng| deal_holdings(&choices, &totals, hands, rng))
+}
+
+// Picks a weighted holding for every player, redealing whenever two holdings share a card
+fn deal_holdings<R: Rng>(choices: &[Vec<([Card; 2], f64)>],
+                         totals: &[f64],
+                         hands: &mut [CardSet],
+                         rng: &mut R) -> CardSet {
+    for _ in 0..MAX_REDEALS {
+        let mut used: CardSet = CardSet::new();
+        let dealt: bool = choices.iter().zip(totals).zip(hands.iter_mut()).all(|((choice, &total), hand)| {
+            let mut pick: f64 = rng.gen::<f64>() * total;
+            let combo: [Card; 2] = choice.iter()
+                .find(|&&(_, weight)| {
+                    pick -= weight;
+                    pick < 0.0
+                })
+                .unwrap_or(&choice[choice.len() - 1])
+                .0;
+            *hand = CardSet::from(&combo[..]);
+            let free: bool = (used & *hand).is_empty();
+            used = used | *hand;
+            free
+        });
+        if dealt {
+            return used;
+        }
+    }
+    panic!("Error: Ranges can't be dealt without sharing cards");
+}
+
+#[cfg(test)]
+mod range_tests {
+    use crate::calc::equity::Equity;
+    use crate::calc::range::{range_equity, ParseRangeError, Range};
+    use crate::card::{parse_cards, Card, CardSet, Name::*, Suit::*};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn size(text: &str) -> usize {
+        text.parse::<Range>().unwrap().len()
+    }
+
+    #[test]
+    fn test_any_two() {
+        let range: Range = Range::any_two();
+        assert_eq!(range.len(), 1326);
+        assert_eq!(range.available(CardSet::from(&parse_cards("AhKd").unwrap())).len(), 1225);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(size("AA"), 6);
+        assert_eq!(size("QQ+"), 18);
+        assert_eq!(size("22-44"), 18);
+        assert_eq!(size("AKs"), 4);
+        assert_eq!(size("AKo"), 12);
+        assert_eq!(size("AK"), 16);
+        assert_eq!(size("KAs"), 4);
+        assert_eq!(size("A2s-A5s"), 16);
+        assert_eq!(size("A5s-A2s"), 16);
+        assert_eq!(size("KTo+"), 36);
+        assert_eq!(size("ATs+"), 16);
+        assert_eq!(size("AhKd"), 1);
+        assert_eq!(size("QQ+, AKs AKo"), 34);
+        assert_eq!(size("AA, AA:0.5"), 6);
+    }
+
+    #[test]
+    fn test_parse_weights() {
+        let range: Range = "AA:0.5, KK".parse().unwrap();
+        let aces: Vec<f64> = range.combos().iter()
+            .filter(|(combo, _)| combo[0].0 == AceHigh)
+            .map(|&(_, weight)| weight)
+            .collect();
+        assert_eq!(aces, vec![0.5; 6]);
+        assert_eq!(range.combos().iter().map(|&(_, weight)| weight).sum::<f64>(), 9.0);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("AA, XK".parse::<Range>(), Err(ParseRangeError::InvalidHand("XK".to_string())));
+        assert_eq!("AAs".parse::<Range>(), Err(ParseRangeError::InvalidHand("AAs".to_string())));
+        assert_eq!("AKs-QJs".parse::<Range>(), Err(ParseRangeError::InvalidHand("AKs-QJs".to_string())));
+        assert_eq!("AhAh".parse::<Range>(), Err(ParseRangeError::InvalidHand("AhAh".to_string())));
+        assert_eq!("AA:2".parse::<Range>(), Err(ParseRangeError::InvalidWeight("AA:2".to_string())));
+        // Jokers can't be dealt as part of a range
+        assert_eq!("*K".parse::<Range>(), Err(ParseRangeError::InvalidHand("*K".to_string())));
+        assert_eq!("**".parse::<Range>(), Err(ParseRangeError::InvalidHand("**".to_string())));
+        assert_eq!("*h*s".parse::<Range>(), Err(ParseRangeError::InvalidHand("*h*s".to_string())));
+        assert_eq!("Ah*s".parse::<Range>(), Err(ParseRangeError::InvalidHand("Ah*s".to_string())));
+    }
+
+    #[test]
+    fn test_card_removal() {
+        let range: Range = "AA, AKs".parse().unwrap();
+        let known: CardSet = CardSet::from(Card(AceHigh, Hearts));
+        assert_eq!(range.available(known).len(), 3 + 3);
+        let known: CardSet = "Ah Kd Ks".parse().unwrap();
+        assert_eq!(range.available(known).len(), 3 + 1);
+    }
+
+    #[test]
+    fn test_hand_vs_range() {
+        let ranges: Vec<Range> = vec!["AsAh".parse().unwrap(), "KK".parse().unwrap()];
+        let results: Vec<Equity> = range_equity(&ranges, &[], &[], 20000, &mut StdRng::seed_from_u64(8));
+        assert!((results[0].equity() - 82.0).abs() < 2.0);
+    }
+
+    #[test]
+    fn test_range_vs_range_board() {
+        // Only sets of kings or a runner-runner straight beat the aces here
+        let ranges: Vec<Range> = vec!["AA".parse().unwrap(), "KK, 72o:0".parse().unwrap()];
+        let board: Vec<Card> = parse_cards("Ks 8d 3c").unwrap();
+        let results: Vec<Equity> = range_equity(&ranges, &board, &[], 5000, &mut StdRng::seed_from_u64(9));
+        assert!(results[1].equity() > 85.0);
+        assert!((results[0].equity() + results[1].equity() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error: Range has no holdings left")]
+    fn test_dead_range() {
+        let ranges: Vec<Range> = vec!["AA".parse().unwrap(), "KK".parse().unwrap()];
+        let dead: Vec<Card> = parse_cards("Ah Ad As").unwrap();
+        range_equity(&ranges, &[], &dead, 1, &mut StdRng::seed_from_u64(10));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::calc::enumerate::combinations;
+use crate::calc::Rank::{self, *};
+use crate::calc::{calc_best_hand, evaluate, HandValue};
+use crate::card::{Card, CardSet, Name::*};
+
+// Rank mask of A-6-7-8-9, the lowest straight once Twos to Fives are taken out
+const ACE_TO_NINE: u16 = 0x10F0;
+
+// House rules that change how short-deck hands rank against each other
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ShortDeckRules {
+    pub trips_beat_straight: bool,
+}
+
+// Value of a short-deck hand, where a flush beats a full house and straights may rank below
+// three of a kind, so hands compare by their short-deck order before their kickers
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ShortDeckValue {
+    order: u8,
+    pub value: HandValue,
+}
+
+// Evaluates a short-deck hand of five to seven cards, returning its value and the five
+// cards chosen
+pub fn evaluate_short_deck(hand: &[Card], rules: ShortDeckRules) -> (ShortDeckValue, Vec<Card>) {
+    assert!((5..=7).contains(&hand.len()), "Error: Short deck hands need 5 to 7 cards");
+    combinations(hand, 5)
+        .map(|five| evaluate_five(&Vec::from(five), rules))
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .unwrap()
+}
+
+fn evaluate_five(five: &[Card], rules: ShortDeckRules) -> (ShortDeckValue, Vec<Card>) {
+    let mut value: HandValue = evaluate(five);
+    let mut chosen: Vec<Card> = calc_best_hand(five, value.rank);
+
+    // A-6-7-8-9 is a Nine high straight with the Ace playing low
+    if CardSet::from(five).rank_mask() == ACE_TO_NINE {
+        value.rank = if value.rank == Flush { StraightFlush } else { Straight };
+        value.kickers = vec![Nine, Eight, Seven, Six, AceLow];
+        chosen = five.to_vec();
+        chosen.sort_by(|a, b| b.cmp_name(a));
+        chosen.rotate_left(1);
+    }
+
+    let order: u8 = order(value.rank, rules);
+    (ShortDeckValue { order, value }, chosen)
+}
+
+fn order(rank: Rank, rules: ShortDeckRules) -> u8 {
+    match rank {
+        HighCard => 0,
+        OnePair => 1,
+        TwoPair => 2,
+        ThreeOfKind if rules.trips_beat_straight => 4,
+        ThreeOfKind => 3,
+        Straight if rules.trips_beat_straight => 3,
+        Straight => 4,
+        FullHouse => 5,
+        Flush => 6,
+        FourOfKind => 7,
+        StraightFlush => 8,
+        RoyalFlush => 9,
+        FiveOfKind => 10,
+    }
+}
+
+#[cfg(test)]
+mod short_deck_tests {
+    use crate::calc::short_deck::{evaluate_short_deck, ShortDeckRules, ShortDeckValue};
+    use crate::calc::Rank::*;
+    use crate::card::{parse_cards, Name::*};
+
+    const STANDARD: ShortDeckRules = ShortDeckRules { trips_beat_straight: false };
+    const TRIPS_HIGH: ShortDeckRules = ShortDeckRules { trips_beat_straight: true };
+
+    fn value(text: &str, rules: ShortDeckRules) -> ShortDeckValue {
+        evaluate_short_deck(&parse_cards(text).unwrap(), rules).0
+    }
+
+    #[test]
+    fn test_flush_beats_full_house() {
+        let flush: ShortDeckValue = value("6h 8h 9h Jh Kh", STANDARD);
+        let full_house: ShortDeckValue = value("Ah Ad As Kc Kd", STANDARD);
+        assert_eq!(flush.value.rank, Flush);
+        assert_eq!(full_house.value.rank, FullHouse);
+        assert!(flush > full_house);
+        assert!(value("7h 7d 7s 7c 6d", STANDARD) > flush);
+    }
+
+    #[test]
+    fn test_ace_to_nine_straight() {
+        let (value, chosen) = evaluate_short_deck(&parse_cards("Ah 6d 7s 8c 9h").unwrap(), STANDARD);
+        assert_eq!(value.value.rank, Straight);
+        assert_eq!(value.value.kickers, vec![Nine, Eight, Seven, Six, AceLow]);
+        assert_eq!(chosen, parse_cards("9h 8c 7s 6d Ah").unwrap());
+        assert!(value < self::value("6h 7d 8s 9c Th", STANDARD));
+        assert!(value > self::value("Ah Ad Ks Kc Qh", STANDARD));
+    }
+
+    #[test]
+    fn test_ace_to_nine_straight_flush() {
+        let straight_flush: ShortDeckValue = value("Ah 6h 7h 8h 9h", STANDARD);
+        assert_eq!(straight_flush.value.rank, StraightFlush);
+        assert!(straight_flush > value("Kh Kd Ks Kc Ah", STANDARD));
+        assert!(straight_flush < value("6h 7h 8h 9h Th", STANDARD));
+        assert_eq!(value("Ah Kh Qh Jh Th", STANDARD).value.rank, RoyalFlush);
+    }
+
+    #[test]
+    fn test_trips_and_straight() {
+        let trips: &str = "8h 8d 8s Kc Ad";
+        let straight: &str = "6h 7d 8s 9c Td";
+        assert!(value(straight, STANDARD) > value(trips, STANDARD));
+        assert!(value(straight, TRIPS_HIGH) < value(trips, TRIPS_HIGH));
+        assert_eq!(ShortDeckRules::default(), STANDARD);
+    }
+
+    #[test]
+    fn test_best_of_seven() {
+        let (value, chosen) = evaluate_short_deck(&parse_cards("Ah Ad 6s 7c 8h 9d Kc").unwrap(), STANDARD);
+        assert_eq!(value.value.rank, Straight);
+        assert_eq!(chosen.len(), 5);
+        assert_eq!(self::value("Qh Qd 6h 9h Th Jh Ks", STANDARD).value.rank, Flush);
+    }
+}
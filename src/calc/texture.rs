@@ -0,0 +1,148 @@
+use crate::calc::enumerate::combinations;
+use crate::calc::lookup::calc_set_strength;
+use crate::calc::has_straight;
+use crate::card::{Card, CardSet, SUITS};
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SuitPattern {
+    // Every board card shares a suit
+    Monotone,
+    // Some board cards share a suit, but not all of them
+    TwoTone,
+    // No two board cards share a suit
+    Rainbow,
+}
+
+impl Display for SuitPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let pattern_str = match self {
+            SuitPattern::Monotone => "Monotone",
+            SuitPattern::TwoTone => "Two-Tone",
+            SuitPattern::Rainbow => "Rainbow",
+        };
+        write!(f, "{}", pattern_str)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Texture {
+    pub paired: bool,
+    pub suits: SuitPattern,
+    // Two board cards are next to each other in rank
+    pub connected: bool,
+    // Some two hole cards make a straight
+    pub straight_possible: bool,
+    // Some two hole cards make a flush
+    pub flush_possible: bool,
+}
+
+impl Display for Texture {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut labels: Vec<String> = vec![self.suits.to_string()];
+        let flags: [(bool, &str); 4] = [(self.paired, "Paired"),
+                                        (self.connected, "Connected"),
+                                        (self.straight_possible, "Straight Possible"),
+                                        (self.flush_possible, "Flush Possible")];
+        labels.extend(flags.iter()
+            .filter(|&&(flag, _)| flag)
+            .map(|&(_, label)| label.to_string()));
+        write!(f, "{}", labels.join(", "))
+    }
+}
+
+// Classifies a flop, turn or river board
+pub fn calc_texture(board: &[Card]) -> Texture {
+    assert!((3..=5).contains(&board.len()), "Error: Texture needs a flop, turn or river");
+    let cards: CardSet = CardSet::from(board);
+    let mask: u16 = cards.rank_mask();
+
+    let most_suited: usize = SUITS.iter()
+        .map(|&suit| cards.suit_mask(suit).count_ones() as usize)
+        .max()
+        .unwrap();
+    let suits: SuitPattern = match most_suited {
+        1 => SuitPattern::Rainbow,
+        n if n == board.len() => SuitPattern::Monotone,
+        _ => SuitPattern::TwoTone,
+    };
+
+    // Ranks with the Ace also played low, as in has_straight
+    let ranks: u16 = mask << 1 | mask >> 12;
+    let straight_possible: bool = (0..13)
+        .any(|first| (first..13).any(|second| has_straight(mask | 1 << first | 1 << second)));
+
+    Texture {
+        paired: (mask.count_ones() as usize) < board.len(),
+        suits,
+        connected: ranks & ranks >> 1 != 0,
+        straight_possible,
+        flush_possible: most_suited >= 3,
+    }
+}
+
+// Counts the holdings an opponent could have that beat a hand on the board
+pub fn count_beating(hole: &[Card], board: &[Card]) -> usize {
+    assert!((3..=5).contains(&board.len()), "Error: Counting needs a flop, turn or river");
+    let board_set: CardSet = CardSet::from(board);
+    let known: CardSet = board_set | CardSet::from(hole);
+    let strength: u16 = calc_set_strength(known);
+    combinations(&Vec::from(CardSet::DECK - known), 2)
+        .filter(|&holding| calc_set_strength(board_set | holding) > strength)
+        .count()
+}
+
+#[cfg(test)]
+mod texture_tests {
+    use crate::calc::texture::{calc_texture, count_beating, SuitPattern, Texture};
+    use crate::card::{parse_cards, Card};
+
+    fn cards(text: &str) -> Vec<Card> {
+        parse_cards(text).unwrap()
+    }
+
+    #[test]
+    fn test_suit_patterns() {
+        assert_eq!(calc_texture(&cards("Ah 7h 2h")).suits, SuitPattern::Monotone);
+        assert_eq!(calc_texture(&cards("Ah 7h 2c")).suits, SuitPattern::TwoTone);
+        assert_eq!(calc_texture(&cards("Ah 7d 2c")).suits, SuitPattern::Rainbow);
+        assert_eq!(calc_texture(&cards("Ah 7h 2h Kc")).suits, SuitPattern::TwoTone);
+        assert!(calc_texture(&cards("Ah 7h 2h")).flush_possible);
+        assert!(!calc_texture(&cards("Ah 7h 2c Kc")).flush_possible);
+    }
+
+    #[test]
+    fn test_dry_board() {
+        let texture: Texture = calc_texture(&cards("Kd 7c 2h"));
+        assert_eq!(texture, Texture {
+            paired: false,
+            suits: SuitPattern::Rainbow,
+            connected: false,
+            straight_possible: false,
+            flush_possible: false,
+        });
+        assert_eq!(texture.to_string(), "Rainbow");
+    }
+
+    #[test]
+    fn test_wet_board() {
+        let texture: Texture = calc_texture(&cards("9h 8h 8c"));
+        assert!(texture.paired);
+        assert!(texture.connected);
+        assert!(!texture.straight_possible);
+        assert_eq!(texture.to_string(), "Two-Tone, Paired, Connected");
+        assert!(calc_texture(&cards("9h 8h 6c")).straight_possible);
+        // The Ace plays low for both connecting and straights
+        assert!(calc_texture(&cards("Ah 2d 9c")).connected);
+        assert!(calc_texture(&cards("Ah 4d 5c")).straight_possible);
+    }
+
+    #[test]
+    fn test_count_beating() {
+        // Only a set of Kings beats middle set of Sevens on a dry board
+        assert_eq!(count_beating(&cards("7d 7s"), &cards("Kd 7c 2h")), 3);
+        assert_eq!(count_beating(&cards("Kh Ks"), &cards("Kd 7c 2h")), 0);
+        // Any pair beats Queen high
+        assert!(count_beating(&cards("Qs Js"), &cards("Kd 7c 2h")) > 100);
+    }
+}
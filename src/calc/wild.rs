@@ -0,0 +1,193 @@
+use crate::calc::enumerate::combinations;
+use crate::calc::lookup::calc_set_strength;
+use crate::calc::Rank::{self, *};
+use crate::calc::{calc_best_hand, evaluate_set, HandValue};
+use crate::card::{Card, CardSet, Name, Name::*, NAMES, SUITS};
+
+// Which cards are wild and what they may stand in for
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Wild {
+    // Jokers can be any card
+    Jokers,
+    // Twos, and any jokers, can be any card
+    Deuces,
+    // Jokers only count as an Ace or to fill a straight or flush
+    Bug,
+}
+
+impl Wild {
+    pub fn is_wild(self, card: Card) -> bool {
+        match self {
+            Wild::Jokers | Wild::Bug => card.0 == Joker,
+            Wild::Deuces => card.0 == Joker || card.0 == Two,
+        }
+    }
+}
+
+// The best hand that can be made with wild cards, the best cards with wild cards replaced by
+// what they became, and each wild card that plays paired with its replacement
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WildHand {
+    pub value: HandValue,
+    pub best_hand: Vec<Card>,
+    pub substitutions: Vec<(Card, Card)>,
+}
+
+// Calculates the best hand of up to seven cards, substituting each wild card for whatever
+// makes the hand strongest
+pub fn calc_best_wild_hand(hand: &[Card], wild: Wild) -> WildHand {
+    let (value, cards, substitutes): (HandValue, Vec<Card>, Vec<(Card, Card)>) = substitute(hand, wild);
+    let best_hand: Vec<Card> = calc_best_hand(&cards, value.rank);
+    let substitutions: Vec<(Card, Card)> = substitutes.into_iter()
+        .filter(|&(_, card)| best_hand.contains(&card))
+        .collect();
+    WildHand { value, best_hand, substitutions }
+}
+
+// Replaces every wild card in a hand of up to seven cards, returning the value of the best
+// replacement, the hand with its wild cards replaced and each wild card paired with what it became
+pub(crate) fn substitute(hand: &[Card], wild: Wild) -> (HandValue, Vec<Card>, Vec<(Card, Card)>) {
+    assert!(hand.len() <= 7, "Error: Wild card hands need at most 7 cards");
+    let wilds: Vec<Card> = hand.iter()
+        .copied()
+        .filter(|&card| wild.is_wild(card))
+        .collect();
+    let mut cards: Vec<Card> = hand.iter()
+        .copied()
+        .filter(|&card| !wild.is_wild(card))
+        .collect();
+
+    if let Some(name) = five_of_kind(&cards, wilds.len(), wild) {
+        let mut substitutes: Vec<(Card, Card)> = five_of_a_kind(name, &cards, &wilds);
+        cards.extend(substitutes.iter().map(|&(_, card)| card));
+        // Only the wild cards needed to reach five play, the rest just repeat the name
+        let held: usize = cards.iter().filter(|card| card.0 == name).count() - substitutes.len();
+        substitutes.truncate(5 - held);
+        let value: HandValue = HandValue { rank: FiveOfKind, kickers: vec![name; 5] };
+        return (value, cards, substitutes);
+    }
+
+    // Without five of a kind there are at most three wild cards, and none of them gains
+    // anything by copying a card already in the hand
+    let held: CardSet = CardSet::from(&cards);
+    let unseen: Vec<Card> = (CardSet::DECK - held).into();
+    let candidates = combinations(&unseen, wilds.len())
+        .filter(|&substitutes| wild != Wild::Bug || bug_plays(held | substitutes, substitutes));
+    // The strength lookup only covers five to seven cards, so shorter hands compare values
+    let substitutes: Option<CardSet> = if hand.len() >= 5 {
+        candidates.max_by_key(|&substitutes| calc_set_strength(held | substitutes))
+    } else {
+        candidates.max_by_key(|&substitutes| evaluate_set(held | substitutes))
+    };
+    let substitutes: CardSet = substitutes.expect("Error: No substitute for the wild cards");
+
+    let value: HandValue = evaluate_set(held | substitutes);
+    let substitutes: Vec<(Card, Card)> = wilds.into_iter()
+        .zip(substitutes)
+        .collect();
+    cards.extend(substitutes.iter().map(|&(_, card)| card));
+    (value, cards, substitutes)
+}
+
+// Finds the highest name the wild cards can make five of, if any
+fn five_of_kind(naturals: &[Card], wilds: usize, wild: Wild) -> Option<Name> {
+    NAMES.iter()
+        .rev()
+        .copied()
+        .filter(|&name| wild != Wild::Bug || name == AceHigh)
+        .find(|&name| naturals.iter().filter(|card| card.0 == name).count() + wilds >= 5)
+}
+
+// Pairs each wild card with a copy of the name, filling in the missing suits first and then
+// repeating them as a real deck would have to
+fn five_of_a_kind(name: Name, naturals: &[Card], wilds: &[Card]) -> Vec<(Card, Card)> {
+    let held: CardSet = naturals.iter()
+        .copied()
+        .filter(|card| card.0 == name)
+        .collect();
+    let missing: Vec<Card> = SUITS.iter()
+        .map(|&suit| Card(name, suit))
+        .filter(|&card| !held.contains(card))
+        .chain(SUITS.iter().map(|&suit| Card(name, suit)))
+        .collect();
+    wilds.iter()
+        .copied()
+        .zip(missing)
+        .collect()
+}
+
+// The bug can always be an Ace, anything else has to finish a straight or flush
+fn bug_plays(cards: CardSet, substitutes: CardSet) -> bool {
+    let rank: Rank = evaluate_set(cards).rank;
+    matches!(rank, Straight | Flush | StraightFlush | RoyalFlush)
+        || substitutes.iter().all(|card| card.0 == AceHigh)
+}
+
+#[cfg(test)]
+mod wild_tests {
+    use crate::calc::wild::{calc_best_wild_hand, Wild, WildHand};
+    use crate::calc::{evaluate, Rank::*};
+    use crate::card::{parse_cards, Card, Name::*, Suit::*};
+
+    fn cards(text: &str) -> Vec<Card> {
+        parse_cards(text).unwrap()
+    }
+
+    #[test]
+    fn test_no_wild_cards() {
+        let hand: Vec<Card> = cards("Ah Kd 7s 7c 2h 9d 3c");
+        let wild_hand: WildHand = calc_best_wild_hand(&hand, Wild::Jokers);
+        assert_eq!(wild_hand.value, evaluate(&hand));
+        assert!(wild_hand.substitutions.is_empty());
+    }
+
+    #[test]
+    fn test_joker_fills_royal_flush() {
+        let wild_hand: WildHand = calc_best_wild_hand(&cards("Ah Kh Qh Jh *s 2c 3d"), Wild::Jokers);
+        assert_eq!(wild_hand.value.rank, RoyalFlush);
+        assert_eq!(wild_hand.substitutions, vec![(Card(Joker, Spades), Card(Ten, Hearts))]);
+        assert!(wild_hand.best_hand.contains(&Card(Ten, Hearts)));
+    }
+
+    #[test]
+    fn test_five_of_a_kind() {
+        let wild_hand: WildHand = calc_best_wild_hand(&cards("Kh Kd Ks Kc *s 2c 3d"), Wild::Jokers);
+        assert_eq!(wild_hand.value.rank, FiveOfKind);
+        assert_eq!(wild_hand.value.kickers, vec![King; 5]);
+        assert_eq!(wild_hand.best_hand.len(), 5);
+        assert!(wild_hand.value > evaluate(&cards("Ah Kh Qh Jh Th")));
+
+        // Four deuces make five of whatever the last card is
+        let wild_hand: WildHand = calc_best_wild_hand(&cards("2h 2d 2s 2c 7d"), Wild::Deuces);
+        assert_eq!(wild_hand.value.kickers, vec![Seven; 5]);
+        assert_eq!(wild_hand.substitutions.len(), 4);
+        let wild_hand: WildHand = calc_best_wild_hand(&cards("2h 2d 2s 2c *h"), Wild::Deuces);
+        assert_eq!(wild_hand.value.kickers, vec![AceHigh; 5]);
+    }
+
+    #[test]
+    fn test_deuces_wild() {
+        let wild_hand: WildHand = calc_best_wild_hand(&cards("2h 2d 9s Ts Js"), Wild::Deuces);
+        assert_eq!(wild_hand.value.rank, StraightFlush);
+        assert_eq!(wild_hand.value.kickers[0], King);
+        assert_eq!(wild_hand.substitutions.len(), 2);
+        assert_eq!(calc_best_wild_hand(&cards("2h 9d 9s 4c 4s"), Wild::Deuces).value.rank, FullHouse);
+        // Without wild rules the deuces are just deuces
+        assert_eq!(calc_best_wild_hand(&cards("2h 2d 9s Ts Js"), Wild::Jokers).value.rank, OnePair);
+    }
+
+    #[test]
+    fn test_bug() {
+        // The bug makes a pair of Aces, but not a pair of Kings
+        let wild_hand: WildHand = calc_best_wild_hand(&cards("*h Ad 8s 5c 3h"), Wild::Bug);
+        assert_eq!(wild_hand.value.rank, OnePair);
+        assert_eq!(wild_hand.value.kickers[0], AceHigh);
+        assert_eq!(calc_best_wild_hand(&cards("*h Kd Ks 8c 3h"), Wild::Bug).value.rank, OnePair);
+        assert_eq!(calc_best_wild_hand(&cards("*h Kd Ks 8c 3h"), Wild::Jokers).value.rank, ThreeOfKind);
+
+        // It still fills straights and flushes
+        assert_eq!(calc_best_wild_hand(&cards("*h 9d Ts Jc Qh"), Wild::Bug).value.rank, Straight);
+        assert_eq!(calc_best_wild_hand(&cards("*h 2s 5s 9s Qs"), Wild::Bug).value.rank, Flush);
+        assert_eq!(calc_best_wild_hand(&cards("*h Ad As Ac Ah"), Wild::Bug).value.rank, FiveOfKind);
+    }
+}
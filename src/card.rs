@@ -1,5 +1,10 @@
 use std::cmp::{Ordering};
 use std::fmt::{Debug, Display, Formatter};
+use std::error::Error;
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, Sub};
+use std::str::FromStr;
+use crate::card::{Name::*, Suit::*};
 
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Name {
@@ -17,6 +22,8 @@ pub enum Name {
     Queen = 12,
     King = 13,
     AceHigh = 14,
+    // Not part of NAMES, jokers only come into play as wild cards
+    Joker = 15,
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -27,9 +34,228 @@ pub enum Suit {
     Clubs = 4,
 }
 
-#[derive(Clone, Copy)]
+pub const NAMES: [Name; 13] = [Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, AceHigh];
+// Names in a 36 card short deck, Twos to Fives removed
+pub const SHORT_NAMES: [Name; 9] = [Six, Seven, Eight, Nine, Ten, Jack, Queen, King, AceHigh];
+pub const SUITS: [Suit; 4] = [Hearts, Diamonds, Spades, Clubs];
+// The red and black jokers of a standard deck
+pub const JOKERS: [Card; 2] = [Card(Joker, Hearts), Card(Joker, Spades)];
+
+impl Name {
+    // Position of the name in NAMES, both aces share the top spot
+    pub(crate) fn index(self) -> usize {
+        match self {
+            AceLow => 12,
+            Joker => panic!("Error: Jokers must be substituted first"),
+            _ => self as usize - 2,
+        }
+    }
+}
+
+impl Suit {
+    // Position of the suit in SUITS
+    pub(crate) fn index(self) -> usize {
+        self as usize - 1
+    }
+}
+
+// Cards are equal only when both name and suit match, and order by name then suit
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Card(pub Name, pub Suit);
 
+impl Card {
+    // Compares two cards by name only, ignoring suits
+    pub fn cmp_name(&self, other: &Card) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+
+    // Checks if two cards share a name, ignoring suits
+    pub fn eq_name(&self, other: &Card) -> bool {
+        self.0 == other.0
+    }
+
+    // Jokers sit above the 52 regular cards, one bit per suit
+    fn bit(self) -> u64 {
+        match self.0 {
+            Joker => 1 << (52 + self.1.index()),
+            _ => 1 << (self.1.index() * 13 + self.0.index()),
+        }
+    }
+}
+
+// A set of cards packed into a u64: the 52 regular cards take the low bits, thirteen ranks per
+// suit, and a joker of each suit takes one of bits 52 to 55
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    pub const EMPTY: CardSet = CardSet(0);
+    pub const DECK: CardSet = CardSet((1 << 52) - 1);
+
+    pub fn new() -> CardSet {
+        CardSet::EMPTY
+    }
+
+    // Adds a card, returns false if it was already in the set
+    pub fn insert(&mut self, card: Card) -> bool {
+        let added: bool = !self.contains(card);
+        self.0 |= card.bit();
+        added
+    }
+
+    // Removes a card, returns false if it wasn't in the set
+    pub fn remove(&mut self, card: Card) -> bool {
+        let removed: bool = self.contains(card);
+        self.0 &= !card.bit();
+        removed
+    }
+
+    pub fn contains(&self, card: Card) -> bool {
+        self.0 & card.bit() != 0
+    }
+
+    pub fn union(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    pub fn intersection(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+
+    pub fn difference(&self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    // Ranks held in a suit, bit 0 is a Two and bit 12 is an Ace
+    pub fn suit_mask(&self, suit: Suit) -> u16 {
+        (self.0 >> (suit.index() * 13)) as u16 & 0x1FFF
+    }
+
+    // Ranks held in any suit
+    pub fn rank_mask(&self) -> u16 {
+        SUITS.iter()
+            .fold(0, |mask, &suit| mask | self.suit_mask(suit))
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    pub(crate) fn from_bits(bits: u64) -> CardSet {
+        CardSet(bits)
+    }
+
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter(self.0)
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, other: CardSet) -> CardSet {
+        self.union(other)
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, other: CardSet) -> CardSet {
+        self.intersection(other)
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, other: CardSet) -> CardSet {
+        self.difference(other)
+    }
+}
+
+impl Debug for CardSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl From<Card> for CardSet {
+    fn from(card: Card) -> CardSet {
+        CardSet(card.bit())
+    }
+}
+
+impl From<&[Card]> for CardSet {
+    fn from(cards: &[Card]) -> CardSet {
+        cards.iter().copied().collect()
+    }
+}
+
+impl From<&Vec<Card>> for CardSet {
+    fn from(cards: &Vec<Card>) -> CardSet {
+        CardSet::from(cards.as_slice())
+    }
+}
+
+impl From<CardSet> for Vec<Card> {
+    fn from(cards: CardSet) -> Vec<Card> {
+        cards.iter().collect()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item=Card>>(iter: I) -> CardSet {
+        let mut cards: CardSet = CardSet::new();
+        iter.into_iter()
+            .for_each(|card| {
+                cards.insert(card);
+            });
+        cards
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+// Walks a CardSet in deck order, suit by suit from Two up to Ace
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+        let bit: usize = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        match bit {
+            0..=51 => Some(Card(NAMES[bit % 13], SUITS[bit / 13])),
+            _ => Some(Card(Joker, SUITS[bit - 52])),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len: usize = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
 impl Debug for Card {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         custom_fmt(*self, f)
@@ -42,75 +268,162 @@ impl Display for Card {
     }
 }
 
-impl Eq for Card {}
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseCardError {
+    InvalidName(String),
+    InvalidSuit(String),
+    InvalidCard(String),
+    Duplicate(String),
+}
 
-impl Ord for Card {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.0.cmp(&other.0)
+impl Display for ParseCardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCardError::InvalidName(token) => write!(f, "Error: Invalid card name '{}'", token),
+            ParseCardError::InvalidSuit(token) => write!(f, "Error: Invalid suit '{}'", token),
+            ParseCardError::InvalidCard(token) => write!(f, "Error: Invalid card '{}'", token),
+            ParseCardError::Duplicate(token) => write!(f, "Error: Duplicate card '{}'", token),
+        }
     }
 }
 
-impl PartialEq for Card {
-    fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
-    }
+impl Error for ParseCardError {}
+
+impl FromStr for Name {
+    type Err = ParseCardError;
 
-    fn ne(&self, other: &Self) -> bool {
-        self.0 != other.0
+    fn from_str(s: &str) -> Result<Name, ParseCardError> {
+        match s.to_ascii_uppercase().as_str() {
+            "2" => Ok(Two),
+            "3" => Ok(Three),
+            "4" => Ok(Four),
+            "5" => Ok(Five),
+            "6" => Ok(Six),
+            "7" => Ok(Seven),
+            "8" => Ok(Eight),
+            "9" => Ok(Nine),
+            "T" | "10" => Ok(Ten),
+            "J" => Ok(Jack),
+            "Q" => Ok(Queen),
+            "K" => Ok(King),
+            "A" => Ok(AceHigh),
+            "*" | "JKR" | "JOKER" => Ok(Joker),
+            _ => Err(ParseCardError::InvalidName(s.to_string())),
+        }
     }
 }
 
-impl PartialOrd for Card {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.0.partial_cmp(&other.0)
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Suit, ParseCardError> {
+        match s {
+            "h" | "H" | "\u{2665}" | "\u{2661}" => Ok(Hearts),
+            "d" | "D" | "\u{2666}" | "\u{2662}" => Ok(Diamonds),
+            "s" | "S" | "\u{2660}" | "\u{2664}" => Ok(Spades),
+            "c" | "C" | "\u{2663}" | "\u{2667}" => Ok(Clubs),
+            _ => Err(ParseCardError::InvalidSuit(s.to_string())),
+        }
     }
+}
+
+// Accepts a name followed by a suit, optionally split by a colon: Ah, Td, 10d, A:H, A♥, *s
+impl FromStr for Card {
+    type Err = ParseCardError;
 
-    fn lt(&self, other: &Self) -> bool {
-        self.0 < other.0
+    fn from_str(s: &str) -> Result<Card, ParseCardError> {
+        let token: &str = s.trim();
+        let (name, suit): (&str, &str) = match token.split_once(':') {
+            Some(parts) => parts,
+            None => match token.char_indices().last() {
+                Some((i, _)) if i > 0 => token.split_at(i),
+                _ => return Err(ParseCardError::InvalidCard(token.to_string())),
+            },
+        };
+        let name: Name = name.parse()
+            .map_err(|_| ParseCardError::InvalidCard(token.to_string()))?;
+        let suit: Suit = suit.parse()
+            .map_err(|_| ParseCardError::InvalidCard(token.to_string()))?;
+        Ok(Card(name, suit))
     }
+}
+
+impl FromStr for CardSet {
+    type Err = ParseCardError;
 
-    fn le(&self, other: &Self) -> bool {
-        self.0 <= other.0
+    fn from_str(s: &str) -> Result<CardSet, ParseCardError> {
+        parse_cards(s).map(|cards| cards.into_iter().collect())
     }
+}
 
-    fn gt(&self, other: &Self) -> bool {
-        self.0 > other.0
+// Parses a hand or board such as "AhKd QsJsTs", cards may be run together or split by spaces or commas
+pub fn parse_cards(text: &str) -> Result<Vec<Card>, ParseCardError> {
+    let mut cards: Vec<Card> = Vec::new();
+    let mut seen: CardSet = CardSet::new();
+
+    for chunk in text.split(|c: char| c.is_whitespace() || c == ',').filter(|chunk| !chunk.is_empty()) {
+        let chars: Vec<(usize, char)> = chunk.char_indices().collect();
+        let mut i: usize = 0;
+        while i < chars.len() {
+            let start: usize = chars[i].0;
+            i += if chars[i].1 == '1' && chars.get(i + 1).map(|c| c.1) == Some('0') { 2 } else { 1 };
+            if chars.get(i).map(|c| c.1) == Some(':') {
+                i += 1;
+            }
+            i += 1;
+            let end: usize = chars.get(i).map_or(chunk.len(), |c| c.0);
+            let token: &str = &chunk[start..end];
+            if i > chars.len() {
+                return Err(ParseCardError::InvalidCard(token.to_string()));
+            }
+            let card: Card = token.parse()?;
+            if !seen.insert(card) {
+                return Err(ParseCardError::Duplicate(token.to_string()));
+            }
+            cards.push(card);
+        }
     }
+    Ok(cards)
+}
 
-    fn ge(&self, other: &Self) -> bool {
-        self.0 >= other.0
+impl Display for Name {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name: &str = match self {
+            Name::AceLow | Name::AceHigh => "A",
+            Name::Two => "2",
+            Name::Three => "3",
+            Name::Four => "4",
+            Name::Five => "5",
+            Name::Six => "6",
+            Name::Seven => "7",
+            Name::Eight => "8",
+            Name::Nine => "9",
+            Name::Ten => "10",
+            Name::Jack => "J",
+            Name::Queen => "Q",
+            Name::King => "K",
+            Name::Joker => "*",
+        };
+        write!(f, "{}", name)
     }
 }
 
 fn custom_fmt(card: Card, f: &mut Formatter<'_>) -> std::fmt::Result {
-    let name: &str = match card.0 {
-        Name::AceLow | Name::AceHigh => "A",
-        Name::Two => "2",
-        Name::Three => "3",
-        Name::Four => "4",
-        Name::Five => "5",
-        Name::Six => "6",
-        Name::Seven => "7",
-        Name::Eight => "8",
-        Name::Nine => "9",
-        Name::Ten => "10",
-        Name::Jack => "J",
-        Name::Queen => "Q",
-        Name::King => "K",
-    };
     let suit: char = match card.1 {
         Suit::Hearts => 'H',
         Suit::Diamonds => 'D',
         Suit::Spades => 'S',
         Suit::Clubs => 'C'
     };
-    write!(f, "{}:{}", name, suit)
+    write!(f, "{}:{}", card.0, suit)
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod card_tests {
-    use crate::card::{Card, Name::*, Suit::*};
+    use crate::card::{parse_cards, Card, CardSet, Name, Name::*, ParseCardError, Suit, Suit::*, JOKERS};
     use std::cmp::Ordering;
+    use std::collections::HashSet;
 
     #[test]
     fn test_new() {
@@ -126,27 +439,48 @@ mod card_tests {
         let card2: Card = Card(Ten, Diamonds);
         let card3: Card = Card(Five, Diamonds);
         let card4: Card = Card(AceHigh, Diamonds);
-        assert_eq!(card1.cmp(&card2), Ordering::Equal);
+        assert_eq!(card1.cmp(&card1), Ordering::Equal);
+        assert_eq!(card1.cmp(&card2), Ordering::Less);
         assert_eq!(card1.cmp(&card3), Ordering::Greater);
         assert_eq!(card1.cmp(&card4), Ordering::Less);
     }
 
+    #[test]
+    fn test_cmp_name() {
+        let card1: Card = Card(Ten, Hearts);
+        let card2: Card = Card(Ten, Diamonds);
+        let card3: Card = Card(Five, Diamonds);
+        let card4: Card = Card(AceHigh, Diamonds);
+        assert_eq!(card1.cmp_name(&card2), Ordering::Equal);
+        assert_eq!(card1.cmp_name(&card3), Ordering::Greater);
+        assert_eq!(card1.cmp_name(&card4), Ordering::Less);
+    }
+
     #[test]
     fn test_eq() {
+        let card1: Card = Card(Ten, Hearts);
+        let card2: Card = Card(Ten, Diamonds);
+        let card3: Card = Card(Ten, Hearts);
+        assert_eq!(card1, card3);
+        assert_eq!(card1 == card2, false);
+    }
+
+    #[test]
+    fn test_eq_name() {
         let card1: Card = Card(Ten, Hearts);
         let card2: Card = Card(Ten, Diamonds);
         let card3: Card = Card(Five, Diamonds);
-        assert_eq!(card1, card2);
-        assert_eq!(card1 == card3, false);
+        assert!(card1.eq_name(&card2));
+        assert_eq!(card1.eq_name(&card3), false);
     }
 
     #[test]
     fn test_ne() {
         let card1: Card = Card(Ten, Hearts);
         let card2: Card = Card(Ten, Diamonds);
-        let card3: Card = Card(Five, Diamonds);
-        assert_eq!(card1 != card2, false);
-        assert_ne!(card1, card3);
+        let card3: Card = Card(Ten, Hearts);
+        assert_ne!(card1, card2);
+        assert_eq!(card1 != card3, false);
     }
 
     #[test]
@@ -155,7 +489,7 @@ mod card_tests {
         let card2: Card = Card(Ten, Diamonds);
         let card3: Card = Card(Five, Diamonds);
         let card4: Card = Card(AceHigh, Diamonds);
-        assert_eq!(card1.partial_cmp(&card2), Some(Ordering::Equal));
+        assert_eq!(card1.partial_cmp(&card2), Some(Ordering::Less));
         assert_eq!(card1.partial_cmp(&card3), Some(Ordering::Greater));
         assert_eq!(card1.partial_cmp(&card4), Some(Ordering::Less));
     }
@@ -165,14 +499,16 @@ mod card_tests {
         let card1: Card = Card(Ten, Hearts);
         let card2: Card = Card(Jack, Diamonds);
         let card3: Card = Card(Five, Diamonds);
+        let card4: Card = Card(Ten, Diamonds);
         assert!(card1 < card2);
+        assert!(card1 < card4);
         assert_eq!(card1 < card3, false);
     }
 
     #[test]
     fn test_le() {
         let card1: Card = Card(Ten, Hearts);
-        let card2: Card = Card(Ten, Diamonds);
+        let card2: Card = Card(Ten, Hearts);
         let card3: Card = Card(Jack, Diamonds);
         let card4: Card = Card(Five, Diamonds);
         assert!(card1 <= card2);
@@ -185,18 +521,158 @@ mod card_tests {
         let card1: Card = Card(Ten, Hearts);
         let card2: Card = Card(Five, Diamonds);
         let card3: Card = Card(Jack, Diamonds);
+        let card4: Card = Card(Ten, Diamonds);
         assert!(card1 > card2);
+        assert!(card4 > card1);
         assert_eq!(card1 > card3, false);
     }
 
     #[test]
     fn test_ge() {
         let card1: Card = Card(Ten, Hearts);
-        let card2: Card = Card(Ten, Diamonds);
+        let card2: Card = Card(Ten, Hearts);
         let card3: Card = Card(Five, Diamonds);
         let card4: Card = Card(Jack, Diamonds);
         assert!(card1 >= card2);
         assert!(card1 >= card3);
         assert_eq!(card1 >= card4, false);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_hash_dedup() {
+        let cards: Vec<Card> = vec![Card(Ten, Hearts),
+                                    Card(Ten, Diamonds),
+                                    Card(Ten, Hearts)];
+        let set: HashSet<Card> = cards.iter().copied().collect();
+        assert_eq!(set.len(), 2);
+
+        let mut sorted: Vec<Card> = cards.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted, vec![Card(Ten, Hearts), Card(Ten, Diamonds)]);
+    }
+
+    #[test]
+    fn test_card_set_insert_remove() {
+        let mut cards: CardSet = CardSet::new();
+        assert!(cards.is_empty());
+        assert!(cards.insert(Card(AceHigh, Spades)));
+        assert_eq!(cards.insert(Card(AceHigh, Spades)), false);
+        assert!(cards.insert(Card(Two, Hearts)));
+        assert!(cards.contains(Card(AceHigh, Spades)));
+        assert!(cards.contains(Card(AceLow, Spades)));
+        assert_eq!(cards.contains(Card(AceHigh, Hearts)), false);
+        assert_eq!(cards.len(), 2);
+        assert!(cards.remove(Card(AceHigh, Spades)));
+        assert_eq!(cards.remove(Card(AceHigh, Spades)), false);
+        assert_eq!(cards.len(), 1);
+    }
+
+    #[test]
+    fn test_card_set_ops() {
+        let hand: CardSet = [Card(Ten, Hearts), Card(Jack, Hearts)].iter().copied().collect();
+        let board: CardSet = [Card(Jack, Hearts), Card(Queen, Clubs)].iter().copied().collect();
+        assert_eq!((hand | board).len(), 3);
+        assert_eq!(hand & board, CardSet::from(Card(Jack, Hearts)));
+        assert_eq!(hand - board, CardSet::from(Card(Ten, Hearts)));
+        assert_eq!(CardSet::DECK.len(), 52);
+        assert_eq!((CardSet::DECK - hand).len(), 50);
+    }
+
+    #[test]
+    fn test_card_set_masks() {
+        let hand: Vec<Card> = vec![Card(AceHigh, Hearts),
+                                   Card(Two, Hearts),
+                                   Card(Two, Clubs),
+                                   Card(Ten, Spades)];
+        let cards: CardSet = CardSet::from(&hand);
+        assert_eq!(cards.suit_mask(Hearts), 0x1001);
+        assert_eq!(cards.suit_mask(Clubs), 0x0001);
+        assert_eq!(cards.suit_mask(Diamonds), 0);
+        assert_eq!(cards.rank_mask(), 0x1101);
+    }
+
+    #[test]
+    fn test_card_set_iter() {
+        let hand: Vec<Card> = vec![Card(Two, Clubs),
+                                   Card(AceHigh, Hearts),
+                                   Card(Ten, Spades),
+                                   Card(Two, Hearts)];
+        let cards: Vec<Card> = CardSet::from(&hand).into();
+        assert_eq!(cards.len(), 4);
+        assert_eq!((cards[0].0, cards[0].1), (Two, Hearts));
+        assert_eq!((cards[1].0, cards[1].1), (AceHigh, Hearts));
+        assert_eq!((cards[2].0, cards[2].1), (Ten, Spades));
+        assert_eq!((cards[3].0, cards[3].1), (Two, Clubs));
+        assert_eq!(CardSet::DECK.iter().count(), 52);
+    }
+
+    #[test]
+    fn test_jokers() {
+        let mut cards: CardSet = CardSet::from(&JOKERS.to_vec());
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards & CardSet::DECK, CardSet::EMPTY);
+        assert_eq!(cards.rank_mask(), 0);
+        assert!(cards.insert(Card(Two, Clubs)));
+        let cards: Vec<Card> = cards.into();
+        assert_eq!(cards, vec![Card(Two, Clubs), Card(Joker, Hearts), Card(Joker, Spades)]);
+        assert_eq!(parse_cards("*h *:S").unwrap(), JOKERS.to_vec());
+        assert_eq!("Joker:S".parse::<Card>(), Ok(Card(Joker, Spades)));
+        assert_eq!(Card(Joker, Spades).to_string(), "*:S");
+    }
+
+    #[test]
+    fn test_parse_name_suit() {
+        assert_eq!("A".parse::<Name>(), Ok(AceHigh));
+        assert_eq!("t".parse::<Name>(), Ok(Ten));
+        assert_eq!("10".parse::<Name>(), Ok(Ten));
+        assert_eq!("1".parse::<Name>(), Err(ParseCardError::InvalidName("1".to_string())));
+        assert_eq!("h".parse::<Suit>(), Ok(Hearts));
+        assert_eq!("D".parse::<Suit>(), Ok(Diamonds));
+        assert_eq!("\u{2660}".parse::<Suit>(), Ok(Spades));
+        assert_eq!("x".parse::<Suit>(), Err(ParseCardError::InvalidSuit("x".to_string())));
+    }
+
+    #[test]
+    fn test_parse_card() {
+        for (text, name, suit) in [("Ah", AceHigh, Hearts),
+                                   ("Td", Ten, Diamonds),
+                                   ("10d", Ten, Diamonds),
+                                   ("A:H", AceHigh, Hearts),
+                                   ("10:C", Ten, Clubs),
+                                   ("K\u{2663}", King, Clubs),
+                                   (" 7s ", Seven, Spades)] {
+            let card: Card = text.parse().unwrap();
+            assert_eq!((card.0, card.1), (name, suit));
+        }
+        assert_eq!("Zh".parse::<Card>().err(), Some(ParseCardError::InvalidCard("Zh".to_string())));
+        assert_eq!("A".parse::<Card>().err(), Some(ParseCardError::InvalidCard("A".to_string())));
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        for card in CardSet::DECK {
+            let parsed: Card = card.to_string().parse().unwrap();
+            assert_eq!((parsed.0, parsed.1), (card.0, card.1));
+        }
+    }
+
+    #[test]
+    fn test_parse_cards() {
+        let cards: Vec<Card> = parse_cards("AhKd QsJsTs").unwrap();
+        let expected: Vec<(Name, Suit)> = vec![(AceHigh, Hearts), (King, Diamonds), (Queen, Spades),
+                                               (Jack, Spades), (Ten, Spades)];
+        assert_eq!(cards.iter().map(|c| (c.0, c.1)).collect::<Vec<_>>(), expected);
+        assert_eq!(parse_cards("10h 10:D, 9\u{2665}9c").unwrap().len(), 4);
+        assert_eq!(parse_cards("").unwrap().len(), 0);
+        assert_eq!("AhKd Qs".parse::<CardSet>().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_parse_cards_errors() {
+        assert_eq!(parse_cards("AhKd Xs"), Err(ParseCardError::InvalidCard("Xs".to_string())));
+        assert_eq!(parse_cards("AhKx"), Err(ParseCardError::InvalidCard("Kx".to_string())));
+        assert_eq!(parse_cards("AhK"), Err(ParseCardError::InvalidCard("K".to_string())));
+        assert_eq!(parse_cards("Ah Kd ah"), Err(ParseCardError::Duplicate("ah".to_string())));
+    }
+}
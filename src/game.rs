@@ -1,12 +1,24 @@
 extern crate rand;
 
-use crate::calc::{calc_rank, Rank, calc_best_hand};
-use crate::card::{Card, Name, Name::*, Suit, Suit::*};
+use crate::betting::{Action, BetError, Betting, Blinds, Structure};
+use crate::calc::{calc_best_hand, evaluate, HandValue};
+use crate::calc::describe::{describe, explain};
+use crate::calc::low::{ace_to_five, eight_or_better, omaha_low, razz_bring_in, stud_bring_in, HiLo, LowValue};
+use crate::calc::omaha::evaluate_omaha;
+use crate::calc::short_deck::{evaluate_short_deck, ShortDeckRules, ShortDeckValue};
+use crate::calc::outs::{calc_outs, Outs};
+use crate::calc::nuts::{calc_nuts, Tier};
+use crate::calc::texture::{calc_texture, count_beating};
+use crate::card::{Card, CardSet, Name, NAMES, SHORT_NAMES, SUITS};
+use crate::pot::{award_hi_lo_pots, award_pots, from_button};
+use crate::strategy::{EquityBot, Strategy, View};
 use rand::{seq::SliceRandom, thread_rng};
+use std::cmp::Reverse;
 use std::collections::VecDeque;
 use std::io;
 use std::io::Write;
 use crate::game::Phase::*;
+use crate::game::Variant::*;
 
 pub fn read_user(output: &str) -> i8 {
     print!("{}", output);
@@ -20,34 +32,160 @@ pub fn read_user(output: &str) -> i8 {
     response
 }
 
+// Reads a number of chips, None when the input isn't one
+pub fn read_chips(output: &str) -> Option<u32> {
+    print!("{}", output);
+    io::stdout().flush().expect("Error: Flush failed");
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).expect("Error: Read failed");
+    input.trim().parse::<u32>().ok()
+}
+
+// Chips each seat buys in for, and again whenever the table needs a rebuy
+const STARTING_STACK: u32 = 1000;
+const BLINDS: Blinds = Blinds { small: 5, big: 10, ante: 0 };
+// Limit games bet the big blind before the turn and double it after, capped at four bets a round
+pub const FIXED_LIMIT: Structure = Structure::FixedLimit { small_bet: BLINDS.big, big_bet: 2 * BLINDS.big, cap: 4 };
+pub const MIN_SEATS: usize = 2;
+pub const MAX_SEATS: usize = 10;
+
+#[derive(Clone, Copy)]
 pub enum Phase {
     Clean,
     DealStud,
     DealTexas,
+    DealOmaha(usize),
     Flop,
     Turn,
     River,
     Winner,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Variant {
+    SevenCardStud,
+    SevenCardStudHiLo,
+    // 7 Card Stud played for ace-to-five low only
+    Razz,
+    TexasHoldem,
+    // Omaha with the given number of hole cards
+    Omaha(usize),
+    OmahaHiLo(usize),
+    // Hold'em with a 36 card deck
+    ShortDeck(ShortDeckRules),
+}
+
+impl Variant {
+    // Names of the cards in the deck the variant plays with
+    fn names(self) -> &'static [Name] {
+        match self {
+            ShortDeck(_) => &SHORT_NAMES,
+            _ => &NAMES,
+        }
+    }
+
+    // Every card the variant deals from
+    pub(crate) fn deck(self) -> CardSet {
+        build_deck(self.names()).into_iter().collect()
+    }
+
+    pub(crate) fn is_stud(self) -> bool {
+        matches!(self, SevenCardStud | SevenCardStudHiLo | Razz)
+    }
+
+    // Most seats the deck has enough cards to deal to
+    fn max_seats(self) -> usize {
+        let (hole_cards, community): (usize, usize) = match self {
+            SevenCardStud | SevenCardStudHiLo | Razz => (7, 0),
+            TexasHoldem | ShortDeck(_) => (2, 5),
+            Omaha(hole_cards) | OmahaHiLo(hole_cards) => (hole_cards, 5),
+        };
+        ((self.names().len() * SUITS.len() - community) / hole_cards).min(MAX_SEATS)
+    }
+}
+
+// A place at the table, played from the keyboard or by the computer with a strategy
+pub struct Seat {
+    pub name: String,
+    strategy: Option<Box<dyn Strategy>>,
+    hole: Vec<Card>,
+}
+
+impl Seat {
+    pub fn human(name: &str) -> Seat {
+        Seat { name: name.to_string(), strategy: None, hole: Vec::new() }
+    }
+
+    pub fn bot(name: &str, strategy: Box<dyn Strategy>) -> Seat {
+        Seat { name: name.to_string(), strategy: Some(strategy), hole: Vec::new() }
+    }
+
+    pub fn is_human(&self) -> bool {
+        self.strategy.is_none()
+    }
+
+    // How the computer plays, None for humans
+    pub fn style(&self) -> Option<&str> {
+        self.strategy.as_ref().map(|strategy| strategy.name())
+    }
+}
+
+// What a hand is worth at showdown, compared the way the variant ranks hands
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Showdown {
+    Standard(HandValue),
+    // Short deck puts some ranks in a different order, so it compares by its own value
+    ShortDeck(ShortDeckValue),
+}
+
+impl Showdown {
+    fn into_value(self) -> HandValue {
+        match self {
+            Showdown::Standard(value) => value,
+            Showdown::ShortDeck(value) => value.value,
+        }
+    }
+}
+
 pub struct Game {
+    variant: Variant,
     deck: VecDeque<Card>,
-    player: Vec<Card>,
-    computer: Vec<Card>,
+    seats: Vec<Seat>,
     community: Vec<Card>,
+    betting: Betting,
+    button: usize,
+    // Actions taken so far this hand, along with the seat that took each
+    history: Vec<(usize, Action)>,
     //burned: Vec<Card>
 }
 
+impl Default for Game {
+    fn default() -> Self {
+        Game::new()
+    }
+}
+
 impl Game {
 
     // Constructor(s)
 
+    // Heads-up against the computer
     pub fn new() -> Game {
+        Game::with_seats(vec![Seat::human("Player"), Seat::bot("Computer", Box::new(EquityBot::default()))])
+    }
+
+    pub fn with_seats(seats: Vec<Seat>) -> Game {
+        assert!((MIN_SEATS..=MAX_SEATS).contains(&seats.len()),
+                "Error: A table needs {} to {} seats", MIN_SEATS, MAX_SEATS);
+        let stacks: Vec<u32> = vec![STARTING_STACK; seats.len()];
         Game {
-            deck: build_deck(),
-            player: Vec::new(),
-            computer: Vec::new(),
+            variant: TexasHoldem,
+            deck: build_deck(&NAMES),
+            button: seats.len() - 1,
+            seats,
             community: Vec::new(),
+            betting: Betting::new(stacks, BLINDS),
+            history: Vec::new(),
             //burned: Vec::new()
         }
     }
@@ -55,109 +193,432 @@ impl Game {
     // Public functions
 
     pub fn play_seven_card_stud(&mut self) {
-        let mut choice: i8;
+        self.set_variant(SevenCardStud);
+        self.play_stud_game();
+    }
+
+    pub fn play_seven_card_stud_hi_lo(&mut self) {
+        self.set_variant(SevenCardStudHiLo);
+        self.play_stud_game();
+    }
+
+    pub fn play_razz(&mut self) {
+        self.set_variant(Razz);
+        self.play_stud_game();
+    }
+
+    pub fn play_texas_holdem(&mut self) {
+        self.set_variant(TexasHoldem);
+        self.play_community_game(DealTexas);
+    }
+
+    pub fn play_omaha(&mut self, hole_cards: usize) {
+        self.set_variant(Omaha(hole_cards));
+        self.play_community_game(DealOmaha(hole_cards));
+    }
+
+    pub fn play_omaha_hi_lo(&mut self, hole_cards: usize) {
+        self.set_variant(OmahaHiLo(hole_cards));
+        self.play_community_game(DealOmaha(hole_cards));
+    }
+
+    pub fn play_short_deck(&mut self, rules: ShortDeckRules) {
+        self.set_variant(ShortDeck(rules));
+        self.play_community_game(DealTexas);
+    }
+
+    // Sets how the games that follow are bet
+    pub fn set_structure(&mut self, structure: Structure) {
+        self.betting.set_structure(structure);
+    }
+
+    // Private functions
+
+    // Switches variant, rebuilding the deck when the new one plays with different cards
+    fn set_variant(&mut self, variant: Variant) {
+        if variant.names() != self.variant.names() {
+            self.clean();
+            self.deck = build_deck(variant.names());
+        }
+        self.variant = variant;
+    }
+
+    // Checks the deck can be dealt to every seat
+    fn fits_table(&self) -> bool {
+        let max_seats: usize = self.variant.max_seats();
+        if self.seats.len() > max_seats {
+            println!("Error: This game can only be dealt to {} seats", max_seats);
+            read_user("Enter) Continue: ");
+        }
+        self.seats.len() <= max_seats
+    }
+
+    // Stud games deal all seven cards at once and have a single betting round, opened by the
+    // bring-in instead of blinds
+    fn play_stud_game(&mut self) {
+        if !self.fits_table() {
+            return;
+        }
         loop {
             // Clear table if needed
             self.update_game(Clean);
+            self.start_stud_hand();
 
-            // Deal
+            // Deal, followed by the round of betting
             self.update_game(DealStud);
-            choice = read_user("Enter) Continue 1) Quit: ");
-            if choice == 1 {
-                break;
+            if !self.betting_round() {
+                return;
             }
 
             // Winner
             self.update_game(Winner);
-            choice = read_user("Enter) Continue 1) Quit: ");
-            if choice == 1 {
+            if read_user("Enter) Continue 1) Quit: ") == 1 {
                 break;
             }
         }
     }
 
-    pub fn play_texas_holdem(&mut self) {
-        let mut choice: i8;
+    fn play_community_game(&mut self, deal: Phase) {
+        if !self.fits_table() {
+            return;
+        }
         loop {
             // Clear table if needed
             self.update_game(Clean);
+            self.start_hand();
 
-            // Deal
-            self.update_game(DealTexas);
-            choice = read_user("Enter) Continue 1) Quit: ");
-            if choice == 1 {
-                break;
+            // Deal, Flop, Turn and River, each followed by a round of betting
+            for &phase in [deal, Flop, Turn, River].iter() {
+                if self.betting.in_hand().len() < 2 {
+                    break;
+                }
+                if !matches!(phase, DealTexas | DealOmaha(_)) {
+                    self.betting.start_round(self.button);
+                }
+                self.update_game(phase);
+                if !self.betting_round() {
+                    return;
+                }
             }
 
-            // Flop
-            self.update_game(Flop);
-            choice = read_user("Enter) Continue 1) Quit: ");
-            if choice == 1 {
+            // Winner
+            self.update_game(Winner);
+            if read_user("Enter) Continue 1) Quit: ") == 1 {
                 break;
             }
+        }
+    }
 
-            // Turn
-            self.update_game(Turn);
-            choice = read_user("Enter) Continue 1) Quit: ");
-            if choice == 1 {
-                break;
+    // Seats the players and posts the blinds
+    fn start_hand(&mut self) {
+        self.seat_players();
+        self.betting.start_hand(self.button);
+        self.history.clear();
+    }
+
+    // Seats the players and deals every seat with chips its seven cards, the door card deciding
+    // who posts the bring-in
+    fn start_stud_hand(&mut self) {
+        self.seat_players();
+        let seated: Vec<usize> = (0..self.seats.len())
+            .filter(|&seat| self.betting.stacks()[seat] > 0)
+            .collect();
+        self.deal(7, &seated);
+        self.betting.start_bring_in(self.bring_in(&seated));
+        self.history.clear();
+    }
+
+    // Moves the button to the next seat with chips. A human out of chips buys back in, while a
+    // busted computer sits out until only one seat has chips left and every busted seat buys
+    // back in. Nobody else's stack changes
+    fn seat_players(&mut self) {
+        let busted: Vec<usize> = (0..self.seats.len())
+            .filter(|&seat| self.betting.stacks()[seat] == 0)
+            .collect();
+        let short_handed: bool = self.seats.len() - busted.len() < 2;
+        for seat in busted {
+            if short_handed || self.seats[seat].is_human() {
+                self.betting.rebuy(seat, STARTING_STACK);
             }
+        }
+        let seats: usize = self.seats.len();
+        self.button = (1..=seats)
+            .map(|offset| (self.button + offset) % seats)
+            .find(|&seat| self.betting.stacks()[seat] > 0)
+            .unwrap();
+    }
 
-            // River
-            self.update_game(River);
-            choice = read_user("Enter) Continue 1) Quit: ");
-            if choice == 1 {
-                break;
+    // The seat that brings it in, going by the door card, the third card dealt and the only one
+    // face up on third street
+    fn bring_in(&self, seats: &[usize]) -> usize {
+        let door_cards: Vec<Card> = seats.iter()
+            .map(|&seat| self.seats[seat].hole[2])
+            .collect();
+        match self.variant {
+            Razz => seats[razz_bring_in(&door_cards)],
+            _ => seats[stud_bring_in(&door_cards)],
+        }
+    }
+
+    // Takes actions until the round is over, false if a player quits
+    fn betting_round(&mut self) -> bool {
+        if self.betting.to_act().is_none() && read_user("Enter) Continue 1) Quit: ") == 1 {
+            self.forfeit();
+            return false;
+        }
+        while let Some(seat) = self.betting.to_act() {
+            let action: Action = match self.seats[seat].is_human() {
+                true => match self.read_action(seat) {
+                    Some(action) => action,
+                    None => {
+                        self.forfeit();
+                        return false;
+                    }
+                },
+                false => self.computer_action(seat),
+            };
+            match self.betting.act(action) {
+                Ok(()) => {
+                    self.history.push((seat, action));
+                    if let Some(style) = self.seats[seat].style() {
+                        println!("{} ({}): {}", self.seats[seat].name, style, action);
+                    }
+                }
+                Err(error) => println!("{}", error),
             }
+        }
+        true
+    }
 
-            // Winner
-            self.update_game(Winner);
-            choice = read_user("Enter) Continue 1) Quit: ");
-            if choice == 1 {
-                break;
+    // Asks a player for an action, None if they quit
+    fn read_action(&self, seat: usize) -> Option<Action> {
+        let to_call: u32 = self.betting.to_call(seat);
+        if self.humans() > 1 {
+            println!("{} to act", self.seats[seat].name);
+        }
+        println!("{}  Pot: {}  Stack: {}  To Call: {}",
+                 self.betting.structure(), self.betting.pot(), self.betting.stacks()[seat], to_call);
+        self.display_stacks();
+        loop {
+            let choice: i8 = match to_call {
+                0 => read_user("1) Fold 2) Check 3) Bet 0) Quit: "),
+                _ => read_user("1) Fold 2) Call 3) Raise 0) Quit: "),
+            };
+            match choice {
+                0 => return None,
+                1 => return Some(Action::Fold),
+                2 if to_call == 0 => return Some(Action::Check),
+                2 => return Some(Action::Call),
+                3 => {
+                    let minimum: u32 = self.betting.min_raise_to().min(self.betting.max_raise_to(seat));
+                    let maximum: u32 = self.betting.max_raise_to(seat);
+                    // Fixed limit leaves only one amount to choose
+                    let total: Option<u32> = match minimum == maximum {
+                        true => Some(maximum),
+                        false => read_chips(&format!("Amount ({}-{}): ", minimum, maximum)),
+                    };
+                    match (total, self.betting.current_bet()) {
+                        (Some(total), 0) => return Some(Action::Bet(total)),
+                        (Some(total), _) => return Some(Action::Raise(total)),
+                        (None, _) => println!("Error: Not a number of chips"),
+                    }
+                }
+                _ => {}
             }
         }
     }
 
-    // Private functions
+    // Quitting mid-hand gives up the pot to the computer seats still in it, or splits it between
+    // the players still in it when there are no computer seats left
+    fn forfeit(&mut self) {
+        let mut winners: Vec<usize> = self.betting.in_hand();
+        if winners.iter().any(|&seat| !self.seats[seat].is_human()) {
+            winners.retain(|&seat| !self.seats[seat].is_human());
+        }
+        self.betting.return_uncalled();
+        self.betting.award(&winners);
+    }
+
+    // Lets a seat's strategy decide, checking or calling instead of anything the rules don't allow
+    fn computer_action(&mut self, seat: usize) -> Action {
+        let mut strategy: Box<dyn Strategy> = self.seats[seat].strategy.take().expect("Error: Seat has no strategy");
+        let betting: &Betting = &self.betting;
+        let view: View = View {
+            seat,
+            hole: &self.seats[seat].hole,
+            board: &self.community,
+            pot: betting.pot(),
+            stacks: betting.stacks(),
+            big_blind: betting.blinds().big,
+            to_call: betting.to_call(seat),
+            current_bet: betting.current_bet(),
+            min_raise_to: betting.min_raise_to(),
+            max_raise_to: betting.max_raise_to(seat),
+            can_raise: betting.can_raise(seat),
+            variant: self.variant,
+            opponents: betting.in_hand().len() - 1,
+            history: &self.history,
+        };
+        let action: Action = strategy.act(&view, &mut thread_rng());
+        let fallback: Action = view.check_or_call();
+        self.seats[seat].strategy = Some(strategy);
+        legal_action(&self.betting, action).unwrap_or(fallback)
+    }
+
+    fn best_hand(&self, hole: &[Card]) -> (Showdown, Vec<Card>) {
+        match self.variant {
+            Omaha(_) | OmahaHiLo(_) => {
+                let (value, best_hand): (HandValue, Vec<Card>) = evaluate_omaha(hole, &self.community);
+                (Showdown::Standard(value), best_hand)
+            }
+            ShortDeck(rules) => {
+                let (value, best_hand): (ShortDeckValue, Vec<Card>) = evaluate_short_deck(&self.hand(hole), rules);
+                (Showdown::ShortDeck(value), best_hand)
+            }
+            SevenCardStud | SevenCardStudHiLo | Razz | TexasHoldem => {
+                let hand: Vec<Card> = self.hand(hole);
+                let value: HandValue = evaluate(&hand);
+                let best_hand: Vec<Card> = calc_best_hand(&hand, value.rank);
+                (Showdown::Standard(value), best_hand)
+            }
+        }
+    }
+
+    // Best eight-or-better low, always None in games without a low half
+    fn best_low(&self, hole: &[Card]) -> Option<LowValue> {
+        match self.variant {
+            OmahaHiLo(_) => omaha_low(hole, &self.community),
+            SevenCardStudHiLo => eight_or_better(&self.hand(hole)),
+            SevenCardStud | Razz | TexasHoldem | Omaha(_) | ShortDeck(_) => None,
+        }
+    }
+
+    // Hole cards together with the community cards
+    fn hand(&self, hole: &[Card]) -> Vec<Card> {
+        let mut hand: Vec<Card> = hole.to_vec();
+        hand.extend(&self.community);
+        hand
+    }
+
+    fn is_hi_lo(&self) -> bool {
+        matches!(self.variant, SevenCardStudHiLo | OmahaHiLo(_))
+    }
+
+    fn humans(&self) -> usize {
+        self.seats.iter().filter(|seat| seat.is_human()).count()
+    }
+
+    // Seat name padded to line up with the cards shown after it
+    fn label(&self, seat: usize) -> String {
+        let width: usize = self.seats.iter()
+            .map(|seat| seat.name.len() + 2)
+            .max()
+            .unwrap()
+            .max(10);
+        format!("{:<width$}", format!("{}:", self.seats[seat].name), width = width)
+    }
+
+    // Names of the given seats, for announcing winners
+    fn names(&self, seats: &[usize]) -> String {
+        let names: Vec<&str> = seats.iter()
+            .map(|&seat| self.seats[seat].name.as_str())
+            .collect();
+        names.join(" and ")
+    }
 
     fn clean(&mut self) {
         if !self.community.is_empty() {
             self.deck.extend(&self.community);
             self.community.clear();
         }
-        if !self.player.is_empty() {
-            self.deck.extend(&self.player);
-            self.player.clear();
-        }
-        if !self.computer.is_empty() {
-            self.deck.extend(&self.computer);
-            self.computer.clear();
+        for seat in self.seats.iter_mut() {
+            self.deck.extend(&seat.hole);
+            seat.hole.clear();
         }
     }
 
-    fn deal(&mut self, n: usize) {
-        let cards: Vec<Card> = self.deck.drain(..(n * 2)).collect();
-        for pair in cards.chunks(2) {
-            self.player.push(pair[0]);
-            self.computer.push(pair[1]);
+    // Deals n cards to each seat one at a time, starting left of the button
+    fn deal(&mut self, n: usize, seats: &[usize]) {
+        let order: Vec<usize> = from_button(seats, self.button, self.seats.len());
+        for _ in 0..n {
+            for &seat in &order {
+                let card: Card = self.deck.pop_front().expect("Error: The deck ran out of cards");
+                self.seats[seat].hole.push(card);
+            }
         }
     }
 
-    fn display_table(&self, show_computer: bool) {
-        println!("Player:   {:?}", self.player);
-
-        if show_computer {
-            println!("Computer: {:?}", self.computer);
+    // Shows the humans' hole cards, and every contender's when show_all is set
+    fn display_table(&self, show_all: bool) {
+        let contenders: Vec<usize> = self.betting.in_hand();
+        for (seat, player) in self.seats.iter().enumerate() {
+            if player.is_human() || (show_all && contenders.contains(&seat)) {
+                println!("{}{:?}", self.label(seat), player.hole);
+            }
         }
         if !self.community.is_empty() {
             println!("Community: {:?}", self.community);
         }
     }
 
+    fn display_stacks(&self) {
+        let stacks: Vec<String> = self.seats.iter()
+            .zip(self.betting.stacks())
+            .map(|(seat, stack)| format!("{} {}", seat.name, stack))
+            .collect();
+        println!("Stacks:   {}", stacks.join("  "));
+    }
+
+    // Shows the computers' door cards and who brought it in
+    fn display_bring_in(&self) {
+        let seats: Vec<usize> = self.betting.in_hand();
+        for &seat in &seats {
+            if !self.seats[seat].is_human() {
+                println!("{}Showing {}", self.label(seat), self.seats[seat].hole[2]);
+            }
+        }
+        let seat: usize = self.bring_in(&seats);
+        println!("{} brings it in for {} with {}", self.seats[seat].name, self.betting.contributed()[seat],
+                 self.seats[seat].hole[2]);
+    }
+
+    fn display_outs(&self) {
+        for seat in self.humans_in_hand() {
+            let outs: Outs = calc_outs(&self.seats[seat].hole, &self.community, None);
+            println!("{}Outs:     {} ({:.1}% next card, {:.1}% by the river)", self.prefix(seat),
+                     outs.improving.len(), outs.next_card * 100.0, outs.by_river * 100.0);
+            for draw in outs.draws {
+                println!("\t{}", draw);
+            }
+        }
+    }
+
+    fn display_texture(&self) {
+        let nuts: Tier = calc_nuts(&self.community);
+        println!("Board:    {}", calc_texture(&self.community));
+        println!("Nuts:     {} ({} combos)", describe(&nuts.value), nuts.holdings.len());
+        for seat in self.humans_in_hand() {
+            println!("{}Beaten by {} combos", self.prefix(seat), count_beating(&self.seats[seat].hole, &self.community));
+        }
+    }
+
+    fn humans_in_hand(&self) -> Vec<usize> {
+        self.betting.in_hand().into_iter()
+            .filter(|&seat| self.seats[seat].is_human())
+            .collect()
+    }
+
+    // Name to tell humans' hand details apart, only needed when more than one is playing
+    fn prefix(&self, seat: usize) -> String {
+        match self.humans() {
+            1 => String::new(),
+            _ => format!("{}: ", self.seats[seat].name),
+        }
+    }
+
     fn shuffle(&mut self) {
         let mut deck_vec: Vec<Card> = self.deck.iter()
-            .map(|&c| c)
+            .copied()
             .collect();
         deck_vec.shuffle(&mut thread_rng());
         self.deck = VecDeque::from(deck_vec);
@@ -176,74 +637,199 @@ impl Game {
                 self.shuffle();
             }
             DealStud => {
-                self.deal(7);
                 self.display_table(false);
+                self.display_bring_in();
             }
             DealTexas => {
-                self.deal(2);
+                self.deal(2, &self.betting.in_hand());
+                self.display_table(false);
+            }
+            DealOmaha(hole_cards) => {
+                self.deal(hole_cards, &self.betting.in_hand());
                 self.display_table(false);
             }
             Flop => {
                 self.update_community(3);
                 self.display_table(false);
+                if self.variant == TexasHoldem {
+                    self.display_texture();
+                    self.display_outs();
+                }
             }
-            Turn | River => {
+            Turn => {
                 self.update_community(1);
                 self.display_table(false);
+                if self.variant == TexasHoldem {
+                    self.display_texture();
+                    self.display_outs();
+                }
+            }
+            River => {
+                self.update_community(1);
+                self.display_table(false);
+                if self.variant == TexasHoldem {
+                    self.display_texture();
+                }
             }
             Winner => {
-                self.display_table(true);
-                self.winner();
+                // Chips nobody called go back before the pot is shown or awarded
+                self.betting.return_uncalled();
+                let pot: u32 = self.betting.pot();
+                if self.betting.in_hand().len() == 1 {
+                    self.display_table(false);
+                    self.fold_winner();
+                } else {
+                    self.display_table(true);
+                    if self.variant == Razz {
+                        self.razz_winner();
+                    } else {
+                        self.winner();
+                    }
+                }
+                if pot > 0 {
+                    println!("Pot: {}", pot);
+                    self.display_stacks();
+                }
             }
         }
     }
 
-    fn winner(&self) {
-        let mut player_hand = self.player.clone();
-        let mut computer_hand = self.computer.clone();
+    // Everyone else folded, so the last seat takes the pot without showing
+    fn fold_winner(&mut self) {
+        let winners: Vec<usize> = self.betting.in_hand();
+        println!("{} Wins, Everyone Else Folded!", self.names(&winners));
+        self.betting.award(&winners);
+    }
 
-        player_hand.extend(&self.community);
-        computer_hand.extend(&self.community);
+    // Awards the main and side pots to the highest values, indexed by seat
+    fn award<V: Ord>(&mut self, values: &[V]) {
+        let shares: Vec<u32> = award_pots(&self.betting.pots(), values, self.button);
+        self.betting.award_shares(&shares);
+    }
 
-        let player_rank: Rank = calc_rank(&player_hand);
-        let computer_rank: Rank = calc_rank(&computer_hand);
+    // Ranks every seat's hand by how many others it beats, so ties share a number
+    fn strengths<V: Ord>(&self, values: &[V]) -> Vec<usize> {
+        (0..values.len())
+            .map(|seat| values.iter().filter(|&other| &values[seat] > other).count())
+            .collect()
+    }
 
-        let player_best_hand: Vec<Card> = calc_best_hand(&player_hand, player_rank);
-        let computer_best_hand: Vec<Card> = calc_best_hand(&computer_hand, computer_rank);
+    // Shows down the contenders' hands, seats that folded or weren't dealt in have no value
+    fn winner(&mut self) {
+        let contenders: Vec<usize> = self.betting.in_hand();
+        let mut showdowns: Vec<Option<Showdown>> = vec![None; self.seats.len()];
+        let mut best_hands: Vec<Vec<Card>> = vec![Vec::new(); self.seats.len()];
+        let mut lows: Vec<Option<LowValue>> = vec![None; self.seats.len()];
+        for &seat in &contenders {
+            let (showdown, best_hand): (Showdown, Vec<Card>) = self.best_hand(&self.seats[seat].hole);
+            showdowns[seat] = Some(showdown);
+            best_hands[seat] = best_hand;
+            lows[seat] = self.best_low(&self.seats[seat].hole);
+        }
+        let strengths: Vec<usize> = self.strengths(&showdowns);
+        let values: Vec<Option<HandValue>> = showdowns.iter()
+            .map(|showdown| showdown.clone().map(Showdown::into_value))
+            .collect();
+        let high: &str = if self.is_hi_lo() { "High: " } else { "" };
 
-        if player_rank > computer_rank {
-            println!("You Win!");
-        } else if player_rank < computer_rank {
-            println!("You Lose!");
-        } else {
-            self.tie_breaker(&player_best_hand, &computer_best_hand);
+        let best: usize = contenders.iter().map(|&seat| strengths[seat]).max().unwrap();
+        let winners: Vec<usize> = contenders.iter()
+            .copied()
+            .filter(|&seat| strengths[seat] == best)
+            .collect();
+        match winners.len() {
+            1 => println!("{}{} Wins!", high, self.names(&winners)),
+            _ => println!("{}{} Tie!", high, self.names(&winners)),
+        }
+
+        // Each human's hand against the best of the others, unless short deck disagrees with the
+        // standard ranking the explanation follows
+        for &seat in contenders.iter().filter(|&&seat| self.seats[seat].is_human()) {
+            let other: usize = contenders.iter()
+                .copied()
+                .filter(|&other| other != seat)
+                .max_by_key(|&other| strengths[other])
+                .unwrap();
+            if let (Some(value), Some(other_value)) = (&values[seat], &values[other]) {
+                if strengths[seat].cmp(&strengths[other]) == value.cmp(other_value) {
+                    let whose: String = match self.humans() {
+                        1 => "Your".to_string(),
+                        _ => format!("{}'s", self.seats[seat].name),
+                    };
+                    println!("{} hand {}", whose, explain(value, other_value));
+                }
+            }
         }
 
-        println!("Player:   {}", player_rank);
-        println!("\t{:?}", player_best_hand);
-        println!("Computer: {}", computer_rank);
-        println!("\t{:?}", computer_best_hand);
+        if self.is_hi_lo() {
+            let best_low: Option<&LowValue> = contenders.iter()
+                .filter_map(|&seat| lows[seat].as_ref())
+                .min();
+            let low_winners: Vec<usize> = contenders.iter()
+                .copied()
+                .filter(|&seat| best_low.is_some() && lows[seat].as_ref() == best_low)
+                .collect();
+            match low_winners.len() {
+                0 => println!("Low:  No Qualifying Low"),
+                1 => println!("Low:  {} Wins!", self.names(&low_winners)),
+                _ => println!("Low:  {} Tie!", self.names(&low_winners)),
+            }
+        }
+
+        for &seat in &contenders {
+            if let Some(value) = &values[seat] {
+                println!("{}{}", self.label(seat), describe(value));
+            }
+            println!("\t{:?}", best_hands[seat]);
+            if let Some(low) = &lows[seat] {
+                println!("\tLow: {}", low);
+            }
+        }
+
+        if self.is_hi_lo() {
+            let hands: Vec<Option<HiLo>> = values.into_iter()
+                .zip(lows)
+                .map(|(high, low)| high.map(|high| HiLo { high, low }))
+                .collect();
+            let shares: Vec<u32> = award_hi_lo_pots(&self.betting.pots(), &hands, self.button);
+            self.betting.award_shares(&shares);
+        } else {
+            self.award(&showdowns);
+        }
     }
 
-    fn tie_breaker(&self, player: &[Card], computer: &[Card]) {
-        for (p, c) in player.iter().zip(computer) {
-            if p > c {
-                println!("You Win!");
-                return;
-            } else if p < c {
-                println!("You Lose!");
-                return;
+    // Razz is won by the best ace-to-five low, pairs and all, among the seats still in the hand
+    fn razz_winner(&mut self) {
+        let contenders: Vec<usize> = self.betting.in_hand();
+        let lows: Vec<Option<LowValue>> = (0..self.seats.len())
+            .map(|seat| match contenders.contains(&seat) {
+                true => Some(ace_to_five(&self.seats[seat].hole)),
+                false => None,
+            })
+            .collect();
+        let best: &LowValue = lows.iter().flatten().min().unwrap();
+        let winners: Vec<usize> = contenders.iter()
+            .copied()
+            .filter(|&seat| lows[seat].as_ref() == Some(best))
+            .collect();
+
+        match winners.len() {
+            1 => println!("{} Wins!", self.names(&winners)),
+            _ => println!("{} Tie!", self.names(&winners)),
+        }
+        for &seat in &contenders {
+            if let Some(low) = &lows[seat] {
+                println!("{}{} Low", self.label(seat), low);
             }
         }
-        println!("It's a Tie!");
+        let values: Vec<Option<Reverse<LowValue>>> = lows.into_iter().map(|low| low.map(Reverse)).collect();
+        self.award(&values);
     }
 }
 
-fn build_deck() -> VecDeque<Card> {
-    let names: [Name; 13] = [Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, AceHigh];
-    let suits: [Suit; 4] = [Hearts, Diamonds, Spades, Clubs];
+fn build_deck(names: &[Name]) -> VecDeque<Card> {
     let mut deck: VecDeque<Card> = VecDeque::new();
-    suits.iter()
+    SUITS.iter()
         .for_each(|&suit| {
             names.iter()
                 .for_each(|&name| {
@@ -251,4 +837,202 @@ fn build_deck() -> VecDeque<Card> {
                 });
         });
     deck
-}
\ No newline at end of file
+}
+
+// Turns an action the rules don't allow into the closest one they do: a check facing a bet calls,
+// a call with nothing to call checks, and a bet or raise is resized to fit the limits
+fn legal_action(betting: &Betting, mut action: Action) -> Option<Action> {
+    // Each fix settles one error, and a resized raise can still need a second fix
+    for _ in 0..3 {
+        let fixed: Action = match (betting.clone().act(action), action) {
+            (Ok(()), _) => return Some(action),
+            (Err(BetError::CannotCheck(_)), _) => Action::Call,
+            (Err(BetError::NothingToCall), _) => Action::Check,
+            (Err(BetError::CannotBet), Action::Bet(total)) => Action::Raise(total),
+            (Err(BetError::NothingToRaise), Action::Raise(total)) => Action::Bet(total),
+            (Err(BetError::BelowMinimum(total)), Action::Bet(_)) => Action::Bet(total),
+            (Err(BetError::BelowMinimum(total)), Action::Raise(_)) => Action::Raise(total),
+            (Err(BetError::NotEnoughChips(total)), Action::Bet(_))
+            | (Err(BetError::AboveLimit(total)), Action::Bet(_)) => Action::Bet(total),
+            (Err(BetError::NotEnoughChips(total)), Action::Raise(_))
+            | (Err(BetError::AboveLimit(total)), Action::Raise(_)) => Action::Raise(total),
+            _ => return None,
+        };
+        action = fixed;
+    }
+    None
+}
+
+#[cfg(test)]
+mod game_tests {
+    use crate::betting::{Action, Betting};
+    use crate::calc::short_deck::ShortDeckRules;
+    use crate::card::{parse_cards, Card, Name::*, Suit::*};
+    use crate::game::{legal_action, Game, Seat, Showdown, Variant::*, BLINDS, FIXED_LIMIT};
+    use crate::strategy::RandomBot;
+
+    fn table(seats: usize) -> Game {
+        let seats: Vec<Seat> = (0..seats)
+            .map(|n| Seat::bot(&format!("Computer {}", n + 1), Box::new(RandomBot)))
+            .collect();
+        Game::with_seats(seats)
+    }
+
+    #[test]
+    fn test_max_seats() {
+        assert_eq!(TexasHoldem.max_seats(), 10);
+        assert_eq!(SevenCardStud.max_seats(), 7);
+        assert_eq!(Omaha(4).max_seats(), 10);
+        assert_eq!(Omaha(5).max_seats(), 9);
+        assert_eq!(Omaha(6).max_seats(), 7);
+        assert_eq!(ShortDeck(Default::default()).max_seats(), 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_too_many_seats() {
+        table(11);
+    }
+
+    #[test]
+    fn test_deal_order() {
+        // Cards go out one at a time starting left of the button, which gets the last card
+        let mut game: Game = table(3);
+        game.button = 1;
+        game.deal(2, &[0, 1, 2]);
+        assert_eq!(game.seats[2].hole, parse_cards("2h 5h").unwrap());
+        assert_eq!(game.seats[0].hole, parse_cards("3h 6h").unwrap());
+        assert_eq!(game.seats[1].hole, parse_cards("4h 7h").unwrap());
+        assert_eq!(game.deck.front(), Some(&Card(Eight, Hearts)));
+
+        // Seats sitting out are skipped
+        game.clean();
+        game.deal(1, &[0, 2]);
+        assert!(game.seats[1].hole.is_empty());
+    }
+
+    #[test]
+    fn test_strengths() {
+        let mut game: Game = table(4);
+        game.community = parse_cards("Kd 7c 2h 9s 4d").unwrap();
+        for (seat, hole) in ["Ks Qs", "7h 7d", "Kh Qh", "3c 5c"].iter().enumerate() {
+            game.seats[seat].hole = parse_cards(hole).unwrap();
+        }
+        let values: Vec<Showdown> = game.seats.iter()
+            .map(|seat| game.best_hand(&seat.hole).0)
+            .collect();
+        // The set beats both tied pairs of Kings, which beat Seven high
+        assert_eq!(game.strengths(&values), vec![1, 3, 1, 0]);
+    }
+
+    #[test]
+    fn test_short_deck_strengths() {
+        let mut game: Game = table(2);
+        game.community = parse_cards("6d 7c 8h Ks Qd").unwrap();
+        game.seats[0].hole = parse_cards("9s Tc").unwrap();
+        game.seats[1].hole = parse_cards("Kh Kc").unwrap();
+        for (trips_beat_straight, strengths) in [(false, vec![1, 0]), (true, vec![0, 1])] {
+            game.variant = ShortDeck(ShortDeckRules { trips_beat_straight });
+            let values: Vec<Showdown> = game.seats.iter()
+                .map(|seat| game.best_hand(&seat.hole).0)
+                .collect();
+            assert_eq!(game.strengths(&values), strengths);
+        }
+    }
+
+    #[test]
+    fn test_showdown_with_busted_seat() {
+        // The busted seat is folded before the deal and gets no cards, so only the other two
+        // show down
+        let mut game: Game = table(3);
+        for variant in [Omaha(4), OmahaHiLo(4)] {
+            game.variant = variant;
+            game.clean();
+            game.betting = Betting::new(vec![1000, 0, 1000], BLINDS);
+            game.button = 2;
+            game.betting.start_hand(game.button);
+            game.deal(4, &game.betting.in_hand());
+            game.update_community(5);
+            assert!(game.seats[1].hole.is_empty());
+            game.winner();
+            assert_eq!(game.betting.stacks().iter().sum::<u32>(), 2000);
+            assert_eq!(game.betting.stacks()[1], 0);
+        }
+    }
+
+    #[test]
+    fn test_rebuy() {
+        let mut seats: Vec<Seat> = vec![Seat::human("Player")];
+        seats.extend((0..3).map(|n| Seat::bot(&format!("Computer {}", n + 1), Box::new(RandomBot))));
+        let mut game: Game = Game::with_seats(seats);
+        let before_blinds = |game: &Game| -> Vec<u32> {
+            game.betting.stacks().iter()
+                .zip(game.betting.contributed())
+                .map(|(stack, contributed)| stack + contributed)
+                .collect()
+        };
+
+        // The busted human buys back in, the busted computer sits out and the leader keeps
+        // their winnings
+        game.betting = Betting::new(vec![0, 2000, 2000, 0], BLINDS);
+        game.start_hand();
+        assert_eq!(before_blinds(&game), vec![1000, 2000, 2000, 0]);
+        assert!(game.betting.has_folded(3));
+
+        // Once only one seat has chips every busted seat buys back in
+        game.betting = Betting::new(vec![4000, 0, 0, 0], BLINDS);
+        game.start_hand();
+        assert_eq!(before_blinds(&game), vec![4000, 1000, 1000, 1000]);
+    }
+
+    #[test]
+    fn test_forfeit_without_computers() {
+        // With nobody but players at the table the pot goes back to them instead of vanishing
+        let seats: Vec<Seat> = vec![Seat::human("Player 1"), Seat::human("Player 2")];
+        let mut game: Game = Game::with_seats(seats);
+        game.start_hand();
+        game.betting.act(Action::Raise(100)).unwrap();
+        game.forfeit();
+        assert_eq!(game.betting.pot(), 0);
+        assert_eq!(game.betting.stacks().iter().sum::<u32>(), 2000);
+    }
+
+    #[test]
+    fn test_legal_action() {
+        let mut betting: Betting = Betting::new(vec![1000, 1000, 1000], BLINDS);
+        betting.start_hand(0);
+        // Facing the big blind a check becomes a call and a raise is resized to fit
+        assert_eq!(legal_action(&betting, Action::Check), Some(Action::Call));
+        assert_eq!(legal_action(&betting, Action::Bet(30)), Some(Action::Raise(30)));
+        assert_eq!(legal_action(&betting, Action::Raise(15)), Some(Action::Raise(20)));
+        assert_eq!(legal_action(&betting, Action::Raise(5000)), Some(Action::Raise(1000)));
+
+        betting.set_structure(FIXED_LIMIT);
+        betting.start_hand(0);
+        assert_eq!(legal_action(&betting, Action::Raise(500)), Some(Action::Raise(20)));
+    }
+
+    #[test]
+    fn test_stud_bring_in() {
+        // Seat 2 is dealt 6h and seat 0 7h face up, the busted seat sits out. The lowest door
+        // card brings it in for high and the highest for Razz
+        for (variant, bring_in) in [(SevenCardStud, 2), (Razz, 0)] {
+            let mut game: Game = table(3);
+            game.variant = variant;
+            game.betting = Betting::new(vec![1000, 0, 1000], BLINDS);
+            game.start_stud_hand();
+            assert!(game.seats[1].hole.is_empty());
+            assert_eq!(game.seats[2].hole[2], Card(Six, Hearts));
+            assert_eq!(game.betting.contributed()[bring_in], BLINDS.small);
+            assert_eq!(game.betting.to_act(), Some(2 - bring_in));
+            game.betting.act(Action::Call).unwrap();
+            assert_eq!(game.betting.to_act(), None);
+            match variant {
+                Razz => game.razz_winner(),
+                _ => game.winner(),
+            }
+            assert_eq!(game.betting.stacks().iter().sum::<u32>(), 2000);
+            assert_eq!(game.betting.stacks()[1], 0);
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod betting;
+pub mod calc;
+pub mod card;
+pub mod game;
+pub mod pot;
+pub mod strategy;
@@ -1,21 +1,95 @@
-mod calc;
-mod card;
-mod game;
-
-use crate::game::{Game, read_user};
+use poker_rust::betting::Structure;
+use poker_rust::calc::short_deck::ShortDeckRules;
+use poker_rust::game::{Game, Seat, read_user, FIXED_LIMIT, MAX_SEATS};
+use poker_rust::strategy::{EquityBot, LooseAggressive, RandomBot, Strategy, TightPassive};
 
 fn main() {
-    let mut games: Game = Game::new();
+    let mut games: Game = Game::with_seats(seats());
     loop {
-        let choice: i8 = read_user("1) 7 Card Stud 2) Texas Hold'em 0) Quit: ");
+        let choice: i8 = read_user("1) 7 Card Stud 2) Texas Hold'em 3) Omaha 4) 5 Card Omaha 5) 6 Card Omaha \
+                                        6) 7 Card Stud Hi/Lo 7) Omaha Hi/Lo 8) Razz 9) Short Deck 0) Quit: ");
         if choice == 0 {
             break;
         }
+        if let 1..=9 = choice {
+            games.set_structure(structure());
+        }
         match choice {
             1 => games.play_seven_card_stud(),
             2 => games.play_texas_holdem(),
+            3 => games.play_omaha(4),
+            4 => games.play_omaha(5),
+            5 => games.play_omaha(6),
+            6 => games.play_seven_card_stud_hi_lo(),
+            7 => games.play_omaha_hi_lo(4),
+            8 => games.play_razz(),
+            9 => games.play_short_deck(short_deck_rules()),
             _ => {}
         }
     }
     println!("Goodbye!");
-}
\ No newline at end of file
+}
+
+// Asks who's playing, one computer opponent unless more are chosen
+fn seats() -> Vec<Seat> {
+    let max: usize = MAX_SEATS - 1;
+    let humans: usize = match read_user(&format!("Players (1-{}): ", max)) {
+        n if n >= 1 && n as usize <= max => n as usize,
+        _ => 1,
+    };
+    let max: usize = MAX_SEATS - humans;
+    let computers: usize = match read_user(&format!("Computer opponents (0-{}): ", max)) {
+        n if n >= 0 && n as usize <= max && humans + n as usize >= 2 => n as usize,
+        _ if humans >= 2 => 0,
+        _ => 1,
+    };
+
+    let style: i8 = match computers {
+        0 => 0,
+        _ => read_user("1) Random 2) Tight-Passive 3) Loose-Aggressive 4) Equity 5) Mixed: "),
+    };
+
+    let mut seats: Vec<Seat> = Vec::new();
+    for n in 1..=humans {
+        seats.push(match humans {
+            1 => Seat::human("Player"),
+            _ => Seat::human(&format!("Player {}", n)),
+        });
+    }
+    for n in 1..=computers {
+        seats.push(match computers {
+            1 => Seat::bot("Computer", strategy(style, n)),
+            _ => Seat::bot(&format!("Computer {}", n), strategy(style, n)),
+        });
+    }
+    seats
+}
+
+// Asks how the game is bet, no limit unless another is chosen
+fn structure() -> Structure {
+    match read_user("1) No Limit 2) Pot Limit 3) Fixed Limit: ") {
+        2 => Structure::PotLimit,
+        3 => FIXED_LIMIT,
+        _ => Structure::NoLimit,
+    }
+}
+
+// Asks how short deck ranks three of a kind against a straight, straights higher unless chosen
+fn short_deck_rules() -> ShortDeckRules {
+    let trips_beat_straight: bool = read_user("1) Straight Beats Trips 2) Trips Beat Straight: ") == 2;
+    ShortDeckRules { trips_beat_straight }
+}
+
+// Builds the chosen bot, Mixed taking turns through every style and anything else playing by equity
+fn strategy(style: i8, n: usize) -> Box<dyn Strategy> {
+    let style: i8 = match style {
+        5 => ((n - 1) % 4) as i8 + 1,
+        _ => style,
+    };
+    match style {
+        1 => Box::new(RandomBot),
+        2 => Box::new(TightPassive),
+        3 => Box::new(LooseAggressive::default()),
+        _ => Box::new(EquityBot::default()),
+    }
+}
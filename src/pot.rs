@@ -0,0 +1,189 @@
+use crate::calc::low::{split_pot, HiLo};
+
+// Chips that only some seats can win, the main pot first and then each side pot
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Pot {
+    pub amount: u32,
+    // Seats still in the hand that put in enough to win it, lowest seat first
+    pub eligible: Vec<usize>,
+}
+
+// Splits what each seat put in over the hand into a main pot and side pots. A new pot starts
+// at every level a seat still in the hand went all-in for, and folded chips stay in the pots
+// they were put in without making the folded seat eligible
+pub fn build_pots(contributed: &[u32], folded: &[bool]) -> Vec<Pot> {
+    let mut levels: Vec<u32> = (0..contributed.len())
+        .filter(|&seat| !folded[seat] && contributed[seat] > 0)
+        .map(|seat| contributed[seat])
+        .collect();
+    levels.sort_unstable();
+    levels.dedup();
+
+    let mut pots: Vec<Pot> = Vec::new();
+    let mut previous: u32 = 0;
+    for &level in &levels {
+        let amount: u32 = contributed.iter()
+            .map(|&chips| chips.min(level) - chips.min(previous))
+            .sum();
+        let eligible: Vec<usize> = (0..contributed.len())
+            .filter(|&seat| !folded[seat] && contributed[seat] >= level)
+            .collect();
+        pots.push(Pot { amount, eligible });
+        previous = level;
+    }
+
+    // Anything folded above the last level can only be won by whoever is left in the top pot,
+    // or by every seat still in the hand when none of them put anything in
+    let leftover: u32 = contributed.iter().map(|&chips| chips - chips.min(previous)).sum();
+    if leftover > 0 {
+        match pots.last_mut() {
+            Some(pot) => pot.amount += leftover,
+            None => {
+                let eligible: Vec<usize> = (0..contributed.len())
+                    .filter(|&seat| !folded[seat])
+                    .collect();
+                pots.push(Pot { amount: leftover, eligible });
+            }
+        }
+    }
+    pots
+}
+
+// Awards every pot to its best eligible hand, values being indexed by seat with higher
+// values winning. Ties split the pot, and odd chips go to the tied seats closest to the
+// left of the button. Returns the chips won by each seat
+pub fn award_pots<V: Ord>(pots: &[Pot], values: &[V], button: usize) -> Vec<u32> {
+    let mut shares: Vec<u32> = vec![0; values.len()];
+    for pot in pots {
+        let eligible: Vec<usize> = from_button(&pot.eligible, button, values.len());
+        let best: &V = match eligible.iter().map(|&seat| &values[seat]).max() {
+            Some(best) => best,
+            None => continue,
+        };
+        let winners: Vec<usize> = eligible.into_iter()
+            .filter(|&seat| &values[seat] == best)
+            .collect();
+        let each: u32 = pot.amount / winners.len() as u32;
+        let odd: usize = (pot.amount % winners.len() as u32) as usize;
+        for (n, &seat) in winners.iter().enumerate() {
+            shares[seat] += each + if n < odd { 1 } else { 0 };
+        }
+    }
+    shares
+}
+
+// Awards every pot half to the best high and half to the best low among its eligible seats,
+// following the same odd chip rules as split_pot with seats counted from the button. Hands
+// are indexed by seat, None for seats that have none to show
+pub fn award_hi_lo_pots(pots: &[Pot], hands: &[Option<HiLo>], button: usize) -> Vec<u32> {
+    let mut shares: Vec<u32> = vec![0; hands.len()];
+    for pot in pots {
+        let eligible: Vec<usize> = from_button(&pot.eligible, button, hands.len());
+        let pot_hands: Vec<HiLo> = eligible.iter()
+            .map(|&seat| hands[seat].clone().expect("Error: Seat eligible for a pot has no hand"))
+            .collect();
+        for (&seat, share) in eligible.iter().zip(split_pot(pot.amount, &pot_hands)) {
+            shares[seat] += share;
+        }
+    }
+    shares
+}
+
+// Orders seats starting with the first one left of the button, the button itself last
+pub(crate) fn from_button(seats: &[usize], button: usize, table: usize) -> Vec<usize> {
+    let mut ordered: Vec<usize> = seats.to_vec();
+    ordered.sort_by_key(|&seat| (seat + table - button - 1) % table);
+    ordered
+}
+
+#[cfg(test)]
+mod pot_tests {
+    use crate::calc::evaluate;
+    use crate::calc::low::{evaluate_hi_lo, HiLo};
+    use crate::calc::HandValue;
+    use crate::card::parse_cards;
+    use crate::pot::{award_hi_lo_pots, award_pots, build_pots, Pot};
+
+    // Pots, values by seat, button and the chips each seat wins
+    type AwardCase = (Vec<Pot>, Vec<u32>, usize, Vec<u32>);
+
+    fn pot(amount: u32, eligible: &[usize]) -> Pot {
+        Pot { amount, eligible: eligible.to_vec() }
+    }
+
+    #[test]
+    fn test_build_pots() {
+        // Contributions, folded seats and the pots they make
+        let cases: Vec<(Vec<u32>, Vec<bool>, Vec<Pot>)> = vec![
+            // Everyone called the same amount
+            (vec![100, 100, 100], vec![false, false, false], vec![pot(300, &[0, 1, 2])]),
+            // One short all-in
+            (vec![50, 100, 100], vec![false, false, false], vec![pot(150, &[0, 1, 2]), pot(100, &[1, 2])]),
+            // Two all-ins of different sizes
+            (vec![25, 60, 100, 100], vec![false; 4],
+             vec![pot(100, &[0, 1, 2, 3]), pot(105, &[1, 2, 3]), pot(80, &[2, 3])]),
+            // Two seats all-in for the same amount share one pot
+            (vec![40, 40, 100, 100], vec![false; 4], vec![pot(160, &[0, 1, 2, 3]), pot(120, &[2, 3])]),
+            // A folded seat's chips stay in the pot without it being eligible
+            (vec![30, 100, 100], vec![true, false, false], vec![pot(230, &[1, 2])]),
+            // A seat that folded after the all-in adds to the side pot too
+            (vec![50, 80, 100, 100], vec![false, true, false, false],
+             vec![pot(200, &[0, 2, 3]), pot(130, &[2, 3])]),
+            // Everyone left is all-in for less than the folded seat put in
+            (vec![40, 100, 40], vec![false, true, false], vec![pot(180, &[0, 2])]),
+            // Only folded seats put anything in, so everyone still in the hand can win it
+            (vec![20, 0, 0], vec![true, false, false], vec![pot(20, &[1, 2])]),
+            // Nothing in the pot
+            (vec![0, 0], vec![false, false], vec![]),
+        ];
+        for (contributed, folded, pots) in cases {
+            assert_eq!(build_pots(&contributed, &folded), pots, "{:?} {:?}", contributed, folded);
+        }
+    }
+
+    #[test]
+    fn test_award_pots() {
+        let cases: Vec<AwardCase> = vec![
+            // Short stack wins the main pot, the best of the rest takes the side pot
+            (vec![pot(150, &[0, 1, 2]), pot(100, &[1, 2])], vec![9, 5, 7], 0, vec![150, 0, 100]),
+            // Best hand overall isn't eligible for the side pot it didn't cover
+            (vec![pot(100, &[0, 1, 2, 3]), pot(105, &[1, 2, 3]), pot(80, &[2, 3])],
+             vec![9, 8, 1, 2], 3, vec![100, 105, 0, 80]),
+            // Tied main pot with an odd chip, first seat left of the button gets it
+            (vec![pot(151, &[0, 1, 2])], vec![5, 5, 1], 0, vec![75, 76, 0]),
+            (vec![pot(151, &[0, 1, 2])], vec![5, 5, 1], 2, vec![76, 75, 0]),
+            // Three way tie with two odd chips, counting round from the button
+            (vec![pot(101, &[0, 1, 2]), pot(50, &[1, 2])], vec![3, 3, 3], 0, vec![33, 59, 59]),
+            // Tied side pot between the two bigger stacks
+            (vec![pot(90, &[0, 1, 2]), pot(61, &[1, 2])], vec![1, 4, 4], 1, vec![0, 75, 76]),
+        ];
+        for (pots, values, button, shares) in cases {
+            let awarded: Vec<u32> = award_pots(&pots, &values, button);
+            assert_eq!(awarded, shares, "{:?} {:?} {}", pots, values, button);
+            assert_eq!(awarded.iter().sum::<u32>(), pots.iter().map(|pot| pot.amount).sum::<u32>());
+        }
+    }
+
+    #[test]
+    fn test_award_hand_values() {
+        let values: Vec<HandValue> = ["Ah Ad Kc Qc 9h 3d 2s", "Kh Kd Ks Qd 9c 3c 2h", "Js Ts 9s 8s 7s 3h 2c"].iter()
+            .map(|text| evaluate(&parse_cards(text).unwrap()))
+            .collect();
+        // Seat 2's straight flush only covers the main pot, the set of Kings takes the side pot
+        let pots: Vec<Pot> = build_pots(&[200, 200, 50], &[false, false, false]);
+        assert_eq!(award_pots(&pots, &values, 0), vec![0, 300, 150]);
+    }
+
+    #[test]
+    fn test_award_hi_lo_pots() {
+        let hands: Vec<Option<HiLo>> = ["Ah 2d 3s 4c 6h Ad Ac", "Kc Ks Qh Qd 9c 9h Js", "As 2h 3c 4d 6s Jd Tc"].iter()
+            .map(|text| Some(evaluate_hi_lo(&parse_cards(text).unwrap())))
+            .collect();
+        // Seat 0 scoops the main pot's high and splits its low with seat 2, taking the odd low
+        // chip as the first seat left of the button. Seat 2 didn't cover the side pot, which
+        // seat 0 scoops with the only low
+        let pots: Vec<Pot> = build_pots(&[100, 100, 21], &[false, false, false]);
+        assert_eq!(pots, vec![pot(63, &[0, 1, 2]), pot(158, &[0, 1])]);
+        assert_eq!(award_hi_lo_pots(&pots, &hands, 2), vec![32 + 16 + 158, 0, 15]);
+    }
+}
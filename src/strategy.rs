@@ -0,0 +1,558 @@
+use crate::betting::Action;
+use crate::calc::describe::made_cards;
+use crate::calc::enumerate::combinations;
+use crate::calc::low::{ace_to_five, evaluate_hi_lo, evaluate_omaha_hi_lo, split_pot, HiLo};
+use crate::calc::omaha::evaluate_omaha;
+use crate::calc::range::{range_equity, Range};
+use crate::calc::short_deck::evaluate_short_deck;
+use crate::calc::{evaluate, HandValue, Rank};
+use crate::card::{Card, CardSet, Name::*};
+use crate::game::Variant::{self, *};
+use rand::{Rng, RngCore};
+use std::cmp::Reverse;
+
+// Run-outs the equity bot deals before each decision
+const EQUITY_TRIALS: u64 = 200;
+
+// What a seat can see when it's their turn to act
+#[derive(Clone, Copy, Debug)]
+pub struct View<'a> {
+    pub seat: usize,
+    pub hole: &'a [Card],
+    pub board: &'a [Card],
+    pub pot: u32,
+    pub stacks: &'a [u32],
+    pub big_blind: u32,
+    pub to_call: u32,
+    pub current_bet: u32,
+    pub min_raise_to: u32,
+    pub max_raise_to: u32,
+    pub can_raise: bool,
+    // The game being played, which decides the deck and how hands rank
+    pub variant: Variant,
+    // Other seats still in the hand
+    pub opponents: usize,
+    // Every action taken this hand in order, along with the seat that took it
+    pub history: &'a [(usize, Action)],
+}
+
+impl View<'_> {
+    pub fn check_or_call(&self) -> Action {
+        match self.to_call {
+            0 => Action::Check,
+            _ => Action::Call,
+        }
+    }
+
+    pub fn check_or_fold(&self) -> Action {
+        match self.to_call {
+            0 => Action::Check,
+            _ => Action::Fold,
+        }
+    }
+
+    // Bets or raises to a total kept within the legal amounts, just calling when raising isn't
+    // allowed
+    pub fn bet_or_raise(&self, total: u32) -> Action {
+        if !self.can_raise {
+            return self.check_or_call();
+        }
+        let total: u32 = total.max(self.min_raise_to).min(self.max_raise_to);
+        match self.current_bet {
+            0 => Action::Bet(total),
+            _ => Action::Raise(total),
+        }
+    }
+
+    // Share of the pot a call has to win to break even
+    pub fn pot_odds(&self) -> f64 {
+        match self.to_call {
+            0 => 0.0,
+            to_call => to_call as f64 / (self.pot + to_call) as f64,
+        }
+    }
+
+    // Bets and raises made by the other seats this hand
+    pub fn aggression(&self) -> usize {
+        self.history.iter()
+            .filter(|&&(seat, action)| seat != self.seat && matches!(action, Action::Bet(_) | Action::Raise(_)))
+            .count()
+    }
+
+    pub fn is_preflop(&self) -> bool {
+        self.board.is_empty()
+    }
+}
+
+// How a computer seat decides what to do
+pub trait Strategy {
+    // Short name shown at the table
+    fn name(&self) -> &str;
+
+    fn act(&mut self, view: &View, rng: &mut dyn RngCore) -> Action;
+}
+
+// Picks any legal action at random
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomBot;
+
+impl Strategy for RandomBot {
+    fn name(&self) -> &str {
+        "Random"
+    }
+
+    fn act(&mut self, view: &View, rng: &mut dyn RngCore) -> Action {
+        let mut actions: Vec<Action> = vec![view.check_or_call()];
+        if view.to_call > 0 {
+            actions.push(Action::Fold);
+        }
+        if view.can_raise {
+            let most: u32 = view.max_raise_to.min(view.min_raise_to + view.pot);
+            let total: u32 = rng.gen_range(view.min_raise_to.min(most)..=most);
+            actions.push(view.bet_or_raise(total));
+        }
+        actions[rng.gen_range(0..actions.len())]
+    }
+}
+
+// Plays few hands and never raises, only calling with strong ones
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TightPassive;
+
+impl Strategy for TightPassive {
+    fn name(&self) -> &str {
+        "Tight-Passive"
+    }
+
+    fn act(&mut self, view: &View, _rng: &mut dyn RngCore) -> Action {
+        if view.is_preflop() {
+            return match starting_strength(view.hole) {
+                2 => view.check_or_call(),
+                1 if view.aggression() == 0 && view.to_call <= view.big_blind => view.check_or_call(),
+                _ => view.check_or_fold(),
+            };
+        }
+        match made_rank(view.hole, view.board, view.variant) {
+            rank if rank >= Rank::TwoPair => view.check_or_call(),
+            Rank::OnePair if view.to_call <= view.pot / 2 => view.check_or_call(),
+            _ => view.check_or_fold(),
+        }
+    }
+}
+
+// Plays most hands and bets them hard, bluffing now and then when checked to
+#[derive(Clone, Copy, Debug)]
+pub struct LooseAggressive {
+    // Chance of betting with nothing when nobody else has bet
+    pub bluff: f64,
+}
+
+impl Default for LooseAggressive {
+    fn default() -> Self {
+        LooseAggressive { bluff: 0.3 }
+    }
+}
+
+impl Strategy for LooseAggressive {
+    fn name(&self) -> &str {
+        "Loose-Aggressive"
+    }
+
+    fn act(&mut self, view: &View, rng: &mut dyn RngCore) -> Action {
+        if view.is_preflop() {
+            return match starting_strength(view.hole) {
+                0 if view.to_call > 2 * view.big_blind => view.check_or_fold(),
+                0 => view.check_or_call(),
+                _ => view.bet_or_raise(3 * view.current_bet.max(view.big_blind)),
+            };
+        }
+        match made_rank(view.hole, view.board, view.variant) {
+            Rank::HighCard if view.to_call == 0 && rng.gen_bool(self.bluff) => view.bet_or_raise(view.pot / 2),
+            Rank::HighCard if view.to_call <= view.pot / 2 => view.check_or_call(),
+            Rank::HighCard => view.check_or_fold(),
+            _ => view.bet_or_raise(view.current_bet + 2 * view.pot / 3),
+        }
+    }
+}
+
+// Estimates its share of the pot against random hands and compares it with the pot odds,
+// raising when well ahead of its fair share
+#[derive(Clone, Copy, Debug)]
+pub struct EquityBot {
+    pub trials: u64,
+}
+
+impl Default for EquityBot {
+    fn default() -> Self {
+        EquityBot { trials: EQUITY_TRIALS }
+    }
+}
+
+impl Strategy for EquityBot {
+    fn name(&self) -> &str {
+        "Equity"
+    }
+
+    fn act(&mut self, view: &View, rng: &mut dyn RngCore) -> Action {
+        let equity: f64 = estimate_equity(view.hole, view.board, view.variant, view.opponents, self.trials, rng);
+        let fair: f64 = 1.0 / (view.opponents + 1) as f64;
+        if equity >= fair + (1.0 - fair) * 0.4 {
+            view.bet_or_raise(view.current_bet + view.pot)
+        } else if equity >= view.pot_odds() {
+            view.check_or_call()
+        } else {
+            view.check_or_fold()
+        }
+    }
+}
+
+// Share of the pot a hand wins on average against random holdings dealt from the variant's
+// deck, from 0 to 1. Hold'em hands are run through range_equity, every other variant is played
+// out by its own rules, with hi/lo games splitting each pot between the high and the low
+pub fn estimate_equity(hole: &[Card],
+                       board: &[Card],
+                       variant: Variant,
+                       opponents: usize,
+                       trials: u64,
+                       rng: &mut dyn RngCore) -> f64 {
+    if opponents == 0 {
+        return 1.0;
+    }
+    let play: PlayOut = PlayOut { hole, board, variant, opponents, trials };
+    match variant {
+        TexasHoldem => {
+            let mut hand: Range = Range::new();
+            hand.add([hole[0], hole[1]], 1.0);
+            let mut ranges: Vec<Range> = vec![hand];
+            ranges.extend((0..opponents).map(|_| Range::any_two()));
+            range_equity(&ranges, board, &[], trials, &mut &mut *rng)[0].equity() / 100.0
+        }
+        ShortDeck(rules) => play.share(rng, |hands, board| {
+            best_share(hands.iter().map(|hand| evaluate_short_deck(&joined(hand, board), rules).0).collect())
+        }),
+        Omaha(_) => play.share(rng, |hands, board| {
+            best_share(hands.iter().map(|hand| evaluate_omaha(hand, board).0).collect())
+        }),
+        OmahaHiLo(_) => play.share(rng, |hands, board| {
+            split_share(hands.iter().map(|hand| evaluate_omaha_hi_lo(hand, board)).collect())
+        }),
+        SevenCardStud => play.share(rng, |hands, _| best_share(hands.iter().map(|hand| evaluate(hand)).collect())),
+        SevenCardStudHiLo => play.share(rng, |hands, _| {
+            split_share(hands.iter().map(|hand| evaluate_hi_lo(hand)).collect())
+        }),
+        Razz => play.share(rng, |hands, _| best_share(hands.iter().map(|hand| Reverse(ace_to_five(hand))).collect())),
+    }
+}
+
+// A hand to play out against random opponents, dealing every hand up to the variant's hole
+// cards and the board up to five community cards
+struct PlayOut<'a> {
+    hole: &'a [Card],
+    board: &'a [Card],
+    variant: Variant,
+    opponents: usize,
+    trials: u64,
+}
+
+impl PlayOut<'_> {
+    // Average share of the pot over every trial, the first hand passed to `share` being ours
+    fn share<F>(&self, rng: &mut dyn RngCore, share: F) -> f64
+        where F: Fn(&[Vec<Card>], &[Card]) -> f64 {
+        let (hole_cards, board_cards): (usize, usize) = match self.variant.is_stud() {
+            true => (7, 0),
+            false => (self.hole.len(), 5),
+        };
+        let known: CardSet = CardSet::from(self.hole) | CardSet::from(self.board);
+        let mut deck: Vec<Card> = (self.variant.deck() - known).into();
+        let needed: usize = hole_cards - self.hole.len() + self.opponents * hole_cards + board_cards - self.board.len();
+        let mut total: f64 = 0.0;
+        for _ in 0..self.trials {
+            for i in 0..needed {
+                let j: usize = rng.gen_range(i..deck.len());
+                deck.swap(i, j);
+            }
+            let (mine, rest): (&[Card], &[Card]) = deck[..needed].split_at(hole_cards - self.hole.len());
+            let (others, run_out): (&[Card], &[Card]) = rest.split_at(self.opponents * hole_cards);
+            let mut hands: Vec<Vec<Card>> = vec![joined(self.hole, mine)];
+            hands.extend(others.chunks(hole_cards).map(|other| other.to_vec()));
+            total += share(&hands, &joined(self.board, run_out));
+        }
+        total / self.trials as f64
+    }
+}
+
+fn joined(first: &[Card], second: &[Card]) -> Vec<Card> {
+    let mut cards: Vec<Card> = first.to_vec();
+    cards.extend(second);
+    cards
+}
+
+// Share of the pot won by the first of the values, ties splitting it
+fn best_share<V: Ord>(values: Vec<V>) -> f64 {
+    let best: &V = values.iter().max().unwrap();
+    match values[0] == *best {
+        true => 1.0 / values.iter().filter(|&value| value == best).count() as f64,
+        false => 0.0,
+    }
+}
+
+// Share of the pot won by the first of the hands, half going to the best high and half to the
+// best low
+fn split_share(hands: Vec<HiLo>) -> f64 {
+    // Divides evenly between up to four winners of each half
+    const POT: u32 = 24;
+    split_pot(POT, &hands)[0] as f64 / POT as f64
+}
+
+// Scores a starting hand 0 for weak, 1 for playable and 2 for premium, going by its best two
+// cards so Omaha hands score too
+pub fn starting_strength(hole: &[Card]) -> u8 {
+    combinations(hole, 2)
+        .map(|pair| {
+            let cards: Vec<Card> = pair.into();
+            two_card_strength(cards[0], cards[1])
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+fn two_card_strength(first: Card, second: Card) -> u8 {
+    let (high, low) = (first.0.max(second.0), first.0.min(second.0));
+    let suited: bool = first.1 == second.1;
+    if (high == low && high >= Ten) || (high == AceHigh && low >= Queen) {
+        2
+    } else if high == low || low >= Ten || (suited && (high == AceHigh || high as u8 - low as u8 == 1)) {
+        1
+    } else {
+        0
+    }
+}
+
+// Rank of the best high hand made with the board, Omaha hands using exactly two hole cards and
+// short deck hands ranked by its own straights. A rank counts as High Card when the board makes
+// the same cards by itself, since everyone shares it and the hole cards at most add kickers. Hi/lo
+// games only look at the high hand, so a made low counts for nothing here
+pub fn made_rank(hole: &[Card], board: &[Card], variant: Variant) -> Rank {
+    let value: HandValue = match variant {
+        Omaha(_) | OmahaHiLo(_) => evaluate_omaha(hole, board).0,
+        ShortDeck(rules) if hole.len() + board.len() >= 5 => evaluate_short_deck(&joined(hole, board), rules).0.value,
+        _ => evaluate(&joined(hole, board)),
+    };
+    let shared: HandValue = match variant {
+        ShortDeck(rules) if board.len() >= 5 => evaluate_short_deck(board, rules).0.value,
+        _ => evaluate(board),
+    };
+    let made: usize = made_cards(value.rank, true);
+    if value.rank == shared.rank && value.kickers[..made] == shared.kickers[..made] {
+        Rank::HighCard
+    } else {
+        value.rank
+    }
+}
+
+#[cfg(test)]
+mod strategy_tests {
+    use crate::betting::{Action, Betting, Blinds};
+    use crate::card::{parse_cards, Card, CardSet};
+    use crate::calc::short_deck::ShortDeckRules;
+    use crate::calc::Rank;
+    use crate::game::Variant::{self, *};
+    use crate::strategy::{estimate_equity, made_rank, starting_strength, EquityBot, LooseAggressive, RandomBot,
+                          Strategy, TightPassive, View};
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    fn cards(text: &str) -> Vec<Card> {
+        parse_cards(text).unwrap()
+    }
+
+    fn view<'a>(hole: &'a [Card], board: &'a [Card], history: &'a [(usize, Action)], to_call: u32) -> View<'a> {
+        View {
+            seat: 0,
+            hole,
+            board,
+            pot: 100,
+            stacks: &[1000, 1000],
+            big_blind: 10,
+            to_call,
+            current_bet: to_call,
+            min_raise_to: 2 * to_call.max(10),
+            max_raise_to: 1000,
+            can_raise: true,
+            variant: TexasHoldem,
+            opponents: 1,
+            history,
+        }
+    }
+
+    #[test]
+    fn test_starting_strength() {
+        assert_eq!(starting_strength(&cards("Ah Kd")), 2);
+        assert_eq!(starting_strength(&cards("Th Td")), 2);
+        assert_eq!(starting_strength(&cards("5h 5d")), 1);
+        assert_eq!(starting_strength(&cards("8h 7h")), 1);
+        assert_eq!(starting_strength(&cards("7h 2d")), 0);
+        // Omaha hands go by their best pair of cards
+        assert_eq!(starting_strength(&cards("7h 2d Ac As")), 2);
+    }
+
+    #[test]
+    fn test_made_rank() {
+        assert_eq!(made_rank(&cards("Ah Kd"), &cards("As 7c 2h"), TexasHoldem), Rank::OnePair);
+        assert_eq!(made_rank(&cards("Ah Kd"), &cards("7s 7c 2h"), TexasHoldem), Rank::HighCard);
+        assert_eq!(made_rank(&cards("7h Kd"), &cards("7s 7c 2h"), TexasHoldem), Rank::ThreeOfKind);
+        assert_eq!(made_rank(&cards("Ah Ad 3c 4d"), &cards("As 7c 2h"), TexasHoldem), Rank::ThreeOfKind);
+        // Aces up beats the board's two pair, a higher kicker alone doesn't
+        assert_eq!(made_rank(&cards("Ah Ad"), &cards("7s 7c 2h 2d"), TexasHoldem), Rank::TwoPair);
+        assert_eq!(made_rank(&cards("Kh 3d"), &cards("7s 7c 2h 2d 5c"), TexasHoldem), Rank::HighCard);
+        assert_eq!(made_rank(&cards("4h 3d"), &cards("7s 7c 2h"), TexasHoldem), Rank::HighCard);
+    }
+
+    #[test]
+    fn test_view_helpers() {
+        let history: Vec<(usize, Action)> = vec![(1, Action::Raise(30)), (0, Action::Raise(90)), (1, Action::Call)];
+        let hole: Vec<Card> = cards("Ah Kd");
+        let view: View = view(&hole, &[], &history, 20);
+        assert_eq!(view.aggression(), 1);
+        assert_eq!(view.check_or_call(), Action::Call);
+        assert_eq!(view.check_or_fold(), Action::Fold);
+        assert!((view.pot_odds() - 20.0 / 120.0).abs() < 1e-9);
+        assert_eq!(view.bet_or_raise(5), Action::Raise(40));
+        assert_eq!(view.bet_or_raise(5000), Action::Raise(1000));
+        assert_eq!(View { can_raise: false, ..view }.bet_or_raise(100), Action::Call);
+    }
+
+    #[test]
+    fn test_tight_passive() {
+        let mut rng: StdRng = StdRng::seed_from_u64(24);
+        let raised: Vec<(usize, Action)> = vec![(1, Action::Raise(30))];
+        let (weak, playable, premium): (Vec<Card>, Vec<Card>, Vec<Card>) = (cards("7h 2d"), cards("8h 7h"), cards("Ah Ad"));
+        assert_eq!(TightPassive.act(&view(&weak, &[], &[], 10), &mut rng), Action::Fold);
+        assert_eq!(TightPassive.act(&view(&playable, &[], &[], 10), &mut rng), Action::Call);
+        assert_eq!(TightPassive.act(&view(&playable, &[], &raised, 20), &mut rng), Action::Fold);
+        assert_eq!(TightPassive.act(&view(&premium, &[], &raised, 20), &mut rng), Action::Call);
+        // Never raises, even with a set
+        let board: Vec<Card> = cards("As 7c 2h");
+        assert_eq!(TightPassive.act(&view(&premium, &board, &[], 0), &mut rng), Action::Check);
+        assert_eq!(TightPassive.act(&view(&weak, &cards("Ks Qc Jh"), &[], 50), &mut rng), Action::Fold);
+        // Two pair on the board isn't worth calling with
+        assert_eq!(TightPassive.act(&view(&cards("4c 4d"), &cards("Ts Qs Qh As Ac"), &[], 50), &mut rng), Action::Fold);
+    }
+
+    #[test]
+    fn test_loose_aggressive() {
+        let mut rng: StdRng = StdRng::seed_from_u64(24);
+        let mut bot: LooseAggressive = LooseAggressive::default();
+        assert_eq!(bot.act(&view(&cards("8h 7h"), &[], &[], 10), &mut rng), Action::Raise(30));
+        assert_eq!(bot.act(&view(&cards("7h 2d"), &[], &[], 10), &mut rng), Action::Call);
+        assert_eq!(bot.act(&view(&cards("7h 2d"), &[], &[], 40), &mut rng), Action::Fold);
+        assert_eq!(bot.act(&view(&cards("Ah Kd"), &cards("As 7c 2h"), &[], 0), &mut rng), Action::Bet(66));
+        // Always bluffs when set to
+        let mut bluffer: LooseAggressive = LooseAggressive { bluff: 1.0 };
+        assert_eq!(bluffer.act(&view(&cards("3h 4d"), &cards("As Kc Jh"), &[], 0), &mut rng), Action::Bet(50));
+    }
+
+    #[test]
+    fn test_equity_bot() {
+        let mut rng: StdRng = StdRng::seed_from_u64(24);
+        let mut bot: EquityBot = EquityBot::default();
+        let board: Vec<Card> = cards("As 7c 2h");
+        // Top set raises, Four high with no draw folds to a pot sized bet
+        assert_eq!(bot.act(&view(&cards("Ah Ad"), &board, &[], 0), &mut rng), Action::Bet(100));
+        assert_eq!(bot.act(&view(&cards("4h 3d"), &cards("Ks Tc 8h"), &[], 100), &mut rng), Action::Fold);
+        // A flush draw has the odds to call a small bet
+        assert_eq!(bot.act(&view(&cards("5h 4h"), &cards("Ah Kh 9c"), &[], 10), &mut rng), Action::Call);
+    }
+
+    #[test]
+    fn test_estimate_equity() {
+        let mut rng: StdRng = StdRng::seed_from_u64(24);
+        let aces: f64 = estimate_equity(&cards("Ah Ad"), &[], TexasHoldem, 1, 1000, &mut rng);
+        assert!((aces - 0.85).abs() < 0.05);
+        assert!(estimate_equity(&cards("Ah Ad"), &[], TexasHoldem, 4, 1000, &mut rng) < aces);
+        let omaha: f64 = estimate_equity(&cards("Ah Ad Kh Kd"), &[], Omaha(4), 1, 500, &mut rng);
+        assert!(omaha > 0.55 && omaha < 0.8);
+        assert_eq!(estimate_equity(&cards("7h 2d"), &[], TexasHoldem, 0, 10, &mut rng), 1.0);
+    }
+
+    #[test]
+    fn test_short_deck_equity() {
+        // Sixes are the lowest pair in short deck, where most opponents hold two higher cards
+        let mut rng: StdRng = StdRng::seed_from_u64(24);
+        let short_deck: Variant = ShortDeck(ShortDeckRules::default());
+        let full: f64 = estimate_equity(&cards("6h 6d"), &[], TexasHoldem, 1, 2000, &mut rng);
+        let short: f64 = estimate_equity(&cards("6h 6d"), &[], short_deck, 1, 2000, &mut rng);
+        assert!(short < full - 0.05, "{} {}", short, full);
+        // A-6-7-8-9 is a straight in short deck and nothing at all in hold'em
+        assert_eq!(made_rank(&cards("Ah 6d"), &cards("7s 8c 9h"), short_deck), Rank::Straight);
+        assert_eq!(made_rank(&cards("Ah 6d"), &cards("7s 8c 9h"), TexasHoldem), Rank::HighCard);
+    }
+
+    #[test]
+    fn test_hi_lo_equity() {
+        let mut rng: StdRng = StdRng::seed_from_u64(24);
+        // The nut low on the board wins half the pot at worst, and never anything for high only
+        let board: Vec<Card> = cards("3c 4d 5s Kh Kd");
+        let hi_lo: f64 = estimate_equity(&cards("Ah 2d 9c 9s"), &board, OmahaHiLo(4), 1, 500, &mut rng);
+        let high: f64 = estimate_equity(&cards("Ah 2d 9c 9s"), &board, Omaha(4), 1, 500, &mut rng);
+        assert!(hi_lo >= 0.5 && hi_lo > high, "{} {}", hi_lo, high);
+        // A wheel is the best Razz hand there is, only ever tied
+        assert!(estimate_equity(&cards("Ah 2d 3c 4s 5h Kc Kd"), &[], Razz, 2, 100, &mut rng) > 0.95);
+        let stud: f64 = estimate_equity(&cards("Kh Kd Ks"), &[], SevenCardStud, 1, 500, &mut rng);
+        assert!(stud > 0.7, "{}", stud);
+    }
+
+    // Bots play whole hands against each other through the betting engine, every action they
+    // choose has to be legal
+    #[test]
+    fn test_bots_act_legally() {
+        let mut rng: StdRng = StdRng::seed_from_u64(24);
+        let mut bots: Vec<Box<dyn Strategy>> = vec![Box::new(RandomBot),
+                                                    Box::new(TightPassive),
+                                                    Box::new(LooseAggressive::default()),
+                                                    Box::new(EquityBot { trials: 20 })];
+        let mut betting: Betting = Betting::new(vec![200; bots.len()], Blinds { small: 5, big: 10, ante: 1 });
+        for hand in 0..40 {
+            if betting.stacks().iter().filter(|&&stack| stack > 0).count() < 2 {
+                betting = Betting::new(vec![200; bots.len()], Blinds { small: 5, big: 10, ante: 1 });
+            }
+            let button: usize = hand % bots.len();
+            let mut deck: Vec<Card> = CardSet::DECK.into();
+            deck.shuffle(&mut rng);
+            let holes: Vec<&[Card]> = deck.chunks(2).take(bots.len()).collect();
+            let board: Vec<Card> = deck[2 * bots.len()..2 * bots.len() + 5].to_vec();
+            let mut history: Vec<(usize, Action)> = Vec::new();
+
+            betting.start_hand(button);
+            for &shown in &[0, 3, 4, 5] {
+                if shown > 0 {
+                    betting.start_round(button);
+                }
+                while let Some(seat) = betting.to_act() {
+                    let view: View = View {
+                        seat,
+                        hole: holes[seat],
+                        board: &board[..shown],
+                        pot: betting.pot(),
+                        stacks: betting.stacks(),
+                        big_blind: 10,
+                        to_call: betting.to_call(seat),
+                        current_bet: betting.current_bet(),
+                        min_raise_to: betting.min_raise_to(),
+                        max_raise_to: betting.max_raise_to(seat),
+                        can_raise: betting.can_raise(seat),
+                        variant: TexasHoldem,
+                        opponents: betting.in_hand().len() - 1,
+                        history: &history,
+                    };
+                    let action: Action = bots[seat].act(&view, &mut rng);
+                    assert_eq!(betting.act(action), Ok(()), "{} chose {}", bots[seat].name(), action);
+                    history.push((seat, action));
+                }
+            }
+            betting.return_uncalled();
+            let winners: Vec<usize> = betting.in_hand();
+            betting.award(&winners[..1]);
+            assert_eq!(betting.stacks().iter().sum::<u32>(), 800);
+        }
+    }
+}